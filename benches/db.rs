@@ -0,0 +1,162 @@
+//! Times the store operations the daemon runs on its hot paths against a
+//! database holding millions of pending entries (10M by default, or
+//! `SWEEPER_BENCH_ROWS`):
+//!
+//! ```shell
+//! cargo bench --bench db
+//! SWEEPER_BENCH_ROWS=100000 cargo bench --bench db
+//! ```
+use rusqlite::params;
+use std::env;
+use std::fs;
+use std::os::unix::ffi::OsStrExt;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use sweeper::state::State;
+use sweeper::store::{now, Entry, SqliteStore, Store, Update};
+
+const DEFAULT_ROWS: u64 = 10_000_000;
+const OPS: u64 = 10_000;
+const SWEEP_BATCH: usize = 1000;
+
+fn report(name: &str, ops: u64, elapsed: Duration) {
+    println!(
+        "{name:<28} {ops:>8} ops in {:>8.2?} ({:>8.2?}/op)",
+        elapsed,
+        elapsed / ops.max(1) as u32
+    );
+}
+
+fn path(n: u64) -> PathBuf {
+    PathBuf::from(format!("/bench/{}/{}", n % 1000, n))
+}
+
+/// Inserts the entries directly in one transaction, going through the store
+/// would take hours.
+fn fill(store: &SqliteStore, rows: u64) {
+    let start = Instant::now();
+    let conn = store.connection();
+    let tx = conn.unchecked_transaction().unwrap();
+    {
+        let mut insert = tx
+            .prepare(
+                "INSERT INTO sweeper (path, name, expire_at, dev, ino, state, created_at, updated_at)
+                    VALUES (?1, 'user.expire_at', ?2, 42, ?3, 'pending', ?4, ?4)",
+            )
+            .unwrap();
+        // Deadlines spread over the next year, none due yet.
+        let now = now();
+        let base = now + 24 * 60 * 60;
+        for i in 0..rows {
+            let expire_at = base + (i * 7919 % (365 * 24 * 60 * 60)) as i64;
+            insert
+                .execute(params![path(i).as_os_str().as_bytes(), expire_at, i, now])
+                .unwrap();
+        }
+    }
+    tx.commit().unwrap();
+    report("fill (one transaction)", rows, start.elapsed());
+}
+
+fn sweep_nothing_due(store: &dyn Store) {
+    let start = Instant::now();
+    for _ in 0..OPS {
+        assert!(store.next_due(SWEEP_BATCH).unwrap().is_empty());
+    }
+    report("sweep, nothing due", OPS, start.elapsed());
+}
+
+fn schedule(store: &dyn Store, rows: u64) {
+    let start = Instant::now();
+    for i in 0..OPS {
+        // Half of them already scheduled, half new files.
+        let n = if i % 2 == 0 { i * 997 % rows } else { rows + i };
+        store
+            .schedule(&path(n), "user.expire_at", now() + 60, 1, n)
+            .unwrap();
+    }
+    report("schedule", OPS, start.elapsed());
+}
+
+fn ingest(store: &dyn Store, rows: u64) {
+    let start = Instant::now();
+    // Batches of 500 like the daemon, half of them replacing an entry.
+    let mut batch = Vec::with_capacity(500);
+    for i in 0..OPS {
+        let n = if i % 2 == 0 {
+            i * 991 % rows
+        } else {
+            rows + OPS + i
+        };
+        batch.push(Entry {
+            path: path(n),
+            name: "user.expire_at".to_string(),
+            expire_at: now() + 60,
+            ..Entry::default()
+        });
+        if batch.len() == 500 || i == OPS - 1 {
+            store.ingest(&batch, "superseded").unwrap();
+            batch.clear();
+        }
+    }
+    report("ingest, batches of 500", OPS, start.elapsed());
+}
+
+fn sweep_due(store: &SqliteStore, rows: u64) {
+    store
+        .connection()
+        .execute(
+            "UPDATE sweeper SET expire_at = 0 WHERE id IN
+                (SELECT id FROM sweeper ORDER BY random() LIMIT ?1)",
+            params![OPS.min(rows)],
+        )
+        .unwrap();
+
+    let start = Instant::now();
+    let mut handled = 0;
+    loop {
+        let due = store.next_due(SWEEP_BATCH).unwrap();
+        if due.is_empty() {
+            break;
+        }
+        // Like the daemon, through `deleting` to `deleted`.
+        for entry in due {
+            let id = entry.id.unwrap();
+            store
+                .transition(id, State::Pending, State::Deleting, Update::default())
+                .unwrap();
+            store
+                .transition(id, State::Deleting, State::Deleted, Update::default())
+                .unwrap();
+            handled += 1;
+        }
+    }
+    report("sweep, due entries", handled, start.elapsed());
+}
+
+fn main() {
+    let rows = env::var("SWEEPER_BENCH_ROWS")
+        .ok()
+        .and_then(|rows| rows.parse().ok())
+        .unwrap_or(DEFAULT_ROWS);
+    let path = env::temp_dir().join("sweeper-bench.db");
+    let remove = || {
+        for suffix in ["", "-wal", "-shm"] {
+            let mut file = path.clone().into_os_string();
+            file.push(suffix);
+            let _ = fs::remove_file(file);
+        }
+    };
+    remove();
+
+    let store = SqliteStore::open(&path).unwrap();
+    println!("{rows} pending entries in {}", path.display());
+    fill(&store, rows);
+    sweep_nothing_due(&store);
+    schedule(&store, rows);
+    ingest(&store, rows);
+    sweep_due(&store, rows);
+
+    drop(store);
+    remove();
+}
@@ -1,15 +1,22 @@
 use libbpf_cargo::{Error, SkeletonBuilder};
-use std::path::Path;
+use std::path::PathBuf;
 
 const BPF_SOURCE: &str = "./src/sweeper.bpf.c";
-const SWEEPER_SKELETON: &str = "./src/sweeper.rs";
+// Generated in OUT_DIR, where `src/lib.rs` includes it from.
+const SWEEPER_SKELETON: &str = "sweeper.skel.rs";
+const FFI_SOURCE: &str = "./src/ffi.rs";
+// Generated in OUT_DIR too, the tracked copy is updated by hand from there
+// and a test makes sure it is.
+const HEADER: &str = "sweeper.h";
+const TRACKED_HEADER: &str = "./include/sweeper.h";
 
 fn main() {
-    let skel = Path::new(SWEEPER_SKELETON);
+    let out_dir = PathBuf::from(std::env::var_os("OUT_DIR").unwrap());
+    let skel = out_dir.join(SWEEPER_SKELETON);
     match SkeletonBuilder::new()
         .source(BPF_SOURCE)
         .clang_args("-Wextra -Wall -Werror")
-        .build_and_generate(skel)
+        .build_and_generate(&skel)
     {
         Ok(_) => {}
         Err(err) => match err {
@@ -20,4 +27,23 @@ fn main() {
     }
 
     println!("cargo:rerun-if-changed={BPF_SOURCE}");
+
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    match cbindgen::generate(crate_dir) {
+        Ok(bindings) => {
+            let header = out_dir.join(HEADER);
+            bindings.write_to_file(&header);
+            if std::fs::read(&header).ok() != std::fs::read(TRACKED_HEADER).ok() {
+                println!(
+                    "cargo:warning={TRACKED_HEADER} is out of date, copy {} over it",
+                    header.display()
+                );
+            }
+        }
+        Err(err) => panic!("{err}"),
+    }
+
+    println!("cargo:rerun-if-changed={FFI_SOURCE}");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed={TRACKED_HEADER}");
 }
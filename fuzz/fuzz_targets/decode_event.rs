@@ -0,0 +1,15 @@
+//! Feeds arbitrary perf buffer samples to the event decoder:
+//!
+//! ```shell
+//! cargo +nightly fuzz run decode_event
+//! ```
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sweeper::event::{decode, EVENT_SIZE};
+
+fuzz_target!(|data: &[u8]| {
+    if decode(data).is_ok() {
+        assert!(data.len() >= EVENT_SIZE);
+    }
+});
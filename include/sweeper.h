@@ -0,0 +1,56 @@
+#ifndef SWEEPER_H
+#define SWEEPER_H
+
+/* Generated by cbindgen from src/ffi.rs, don't edit by hand. */
+
+#include <stdint.h>
+
+/**
+ * Makes `path` expire at `epoch`, in seconds, by setting its
+ * `user.expire_at` xattr, or the one named in the config file, which the
+ * running daemon picks up. Relative paths are made absolute.
+ *
+ * Returns 0, or a negative `errno`: `-EINVAL` for a path the daemon
+ * couldn't read in full, a timestamp before the epoch or an invalid config
+ * file.
+ *
+ * # Safety
+ *
+ * `path` must be NULL or point to a NUL-terminated string.
+ */
+int32_t sweeper_set_expiry(const char *path, int64_t epoch);
+
+/**
+ * Makes `path` expire in `seconds` from now. Returns the timestamp it was
+ * set to, or a negative `errno` like `sweeper_set_expiry`.
+ *
+ * # Safety
+ *
+ * `path` must be NULL or point to a NUL-terminated string.
+ */
+int64_t sweeper_set_ttl(const char *path, uint64_t seconds);
+
+/**
+ * Removes the xattr `sweeper_set_expiry` sets from `path`, so the daemon
+ * leaves it alone. Returns 1 if it had one, 0 if it didn't, or a negative
+ * `errno`.
+ *
+ * # Safety
+ *
+ * `path` must be NULL or point to a NUL-terminated string.
+ */
+int32_t sweeper_cancel(const char *path);
+
+/**
+ * Reads the xattr `sweeper_set_expiry` sets on `path` the way the daemon
+ * does, storing it in `*epoch`. Returns 1 if it has one, 0 if it doesn't,
+ * or a negative `errno`: `-EINVAL` if the value isn't a timestamp.
+ *
+ * # Safety
+ *
+ * `path` must be NULL or point to a NUL-terminated string, and `epoch`
+ * must be NULL or point to an `int64_t`.
+ */
+int32_t sweeper_query(const char *path, int64_t *epoch);
+
+#endif /* SWEEPER_H */
@@ -0,0 +1,671 @@
+use crate::quarantine::{Quarantine, DEFAULT_GRACE};
+use crate::rules::parse_age;
+use crate::target::Target;
+use crate::xattr;
+use std::fs::{self, File, OpenOptions, Permissions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::os::unix::fs::{fchown, MetadataExt, OpenOptionsExt, PermissionsExt};
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const DEFAULT_ACTION: &str = "delete";
+
+/// Every kind of action `parse_action` knows about.
+pub const ACTIONS: &[&str] = &[
+    "delete",
+    "truncate",
+    "chmod",
+    "gzip",
+    "zstd",
+    "quarantine",
+    "shred",
+    "trash",
+    "archive",
+];
+
+/// Kinds of actions writing to a path given in the spec. They run as root,
+/// so only rules may ask for them, never the owner of the file.
+pub const PATH_ACTIONS: &[&str] = &["trash", "archive"];
+
+/// Kinds of actions that can't be done again after being interrupted: the
+/// file could end up in the archive twice, or half overwritten by a shred.
+pub const ONE_SHOT_ACTIONS: &[&str] = &["archive", "shred"];
+
+pub const DEFAULT_SHRED_PASSES: u32 = 3;
+const SHRED_BUFFER_LEN: usize = 64 * 1024;
+
+// A tar archive ends with two zeroed 512 byte blocks.
+const TAR_TRAILER_LEN: u64 = 1024;
+
+/// Set on a compressed copy to the device and inode of the file it's made
+/// from, so a retry knows a leftover copy is its own.
+pub const COMPRESSED_FROM: &str = "user.sweeper.compressed_from";
+
+/// What an action did with the file, besides succeeding.
+#[derive(Debug, Default)]
+pub struct Outcome {
+    /// Where the file was moved to, if it still exists somewhere else.
+    pub moved_to: Option<PathBuf>,
+    /// Set when the moved file has to be purged after this long.
+    pub purge_after: Option<Duration>,
+    /// Disk space given back, as far as we can tell.
+    pub bytes_freed: u64,
+}
+
+/// Bytes that unlinking the file frees, which is nothing while it has
+/// other hard links.
+fn unlinked_bytes(metadata: &fs::Metadata) -> u64 {
+    if metadata.nlink() > 1 {
+        0
+    } else {
+        metadata.len()
+    }
+}
+
+/// Opens the regular file `target` with `flags`, refusing a symlink, which
+/// it could have been swapped for since it was checked. The action then
+/// works on the fd.
+fn open_regular(target: &Target, flags: libc::c_int) -> io::Result<(File, fs::Metadata)> {
+    let file = target.open_file(flags)?;
+    let metadata = file.metadata()?;
+    if !metadata.is_file() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "only regular files can be expired this way",
+        ));
+    }
+    Ok((file, metadata))
+}
+
+/// Fills the newly created `output` with what `write` puts in it, and gives
+/// it the owner and permissions of the file `metadata` is from. Left behind
+/// if that fails, for the caller to remove. Returns its size.
+fn copy_into(
+    metadata: &fs::Metadata,
+    output: File,
+    write: impl FnOnce(File) -> io::Result<File>,
+) -> io::Result<u64> {
+    let output = write(output)?;
+    fchown(&output, Some(metadata.uid()), Some(metadata.gid()))?;
+    output.set_permissions(metadata.permissions())?;
+    output.sync_all()?;
+    Ok(output.metadata()?.len())
+}
+
+/// What happens to a file once it expires.
+pub trait Action: Send + Sync {
+    /// The spec this action was parsed from, as stored in the DB.
+    fn spec(&self) -> String;
+    fn apply(&self, target: &Target) -> io::Result<Outcome>;
+
+    /// For actions moving the file somewhere it has to be found again,
+    /// where it goes and for how long, to be recorded before `apply_to`
+    /// moves it there.
+    fn destination(&self, _target: &Target) -> io::Result<Option<(PathBuf, Duration)>> {
+        Ok(None)
+    }
+
+    /// Applies the action, moving the file to a `destination` it returned.
+    fn apply_to(&self, target: &Target, _destination: &Path) -> io::Result<Outcome> {
+        self.apply(target)
+    }
+}
+
+pub struct Delete;
+
+impl Action for Delete {
+    fn spec(&self) -> String {
+        "delete".to_string()
+    }
+
+    fn apply(&self, target: &Target) -> io::Result<Outcome> {
+        let metadata = target.metadata()?;
+        target.unlink()?;
+        Ok(Outcome {
+            bytes_freed: unlinked_bytes(&metadata),
+            ..Outcome::default()
+        })
+    }
+}
+
+/// Moves the file under `dir`, keeping its full original path, e.g.
+/// `/home/me/bye` ends up in `<dir>/home/me/bye`.
+pub struct Trash {
+    pub dir: PathBuf,
+}
+
+impl Action for Trash {
+    fn spec(&self) -> String {
+        format!("trash:{}", self.dir.display())
+    }
+
+    fn apply(&self, target: &Target) -> io::Result<Outcome> {
+        let path = target.path();
+        let relative = path.strip_prefix("/").unwrap_or(path);
+        let destination = self.dir.join(relative);
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        match target.rename_to(&destination) {
+            Err(e) if e.raw_os_error() == Some(libc::EXDEV) => {
+                let (mut input, metadata) = open_regular(target, libc::O_RDONLY)?;
+                // `create_new` fails on an existing symlink too.
+                let output = OpenOptions::new()
+                    .write(true)
+                    .create_new(true)
+                    .mode(0o600)
+                    .open(&destination)?;
+                let copied = copy_into(&metadata, output, |mut output| {
+                    io::copy(&mut input, &mut output)?;
+                    Ok(output)
+                });
+                if let Err(e) = copied {
+                    let _ = fs::remove_file(&destination);
+                    return Err(e);
+                }
+                target.unlink()?;
+            }
+            result => result?,
+        }
+
+        Ok(Outcome {
+            moved_to: Some(destination),
+            ..Outcome::default()
+        })
+    }
+}
+
+pub struct Truncate;
+
+impl Action for Truncate {
+    fn spec(&self) -> String {
+        "truncate".to_string()
+    }
+
+    fn apply(&self, target: &Target) -> io::Result<Outcome> {
+        let (file, metadata) = open_regular(target, libc::O_WRONLY)?;
+        let len = metadata.len();
+        file.set_len(0)?;
+        Ok(Outcome {
+            bytes_freed: len,
+            ..Outcome::default()
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    Zstd,
+}
+
+/// Replaces the file with a compressed copy next to it (`<path>.gz` or
+/// `<path>.zst`) with the same owner and permissions. The copy is tagged
+/// with the file it's made from, so one left behind by an interrupted
+/// attempt is made again rather than mistaken for someone else's.
+pub struct Compress {
+    pub format: Compression,
+}
+
+impl Action for Compress {
+    fn spec(&self) -> String {
+        match self.format {
+            Compression::Gzip => "gzip".to_string(),
+            Compression::Zstd => "zstd".to_string(),
+        }
+    }
+
+    fn apply(&self, target: &Target) -> io::Result<Outcome> {
+        let destination = target.sibling(match self.format {
+            Compression::Gzip => ".gz",
+            Compression::Zstd => ".zst",
+        })?;
+
+        let (mut input, metadata) = open_regular(target, libc::O_RDONLY)?;
+        let source = format!("{}:{}", metadata.dev(), metadata.ino());
+        // What an interrupted attempt left behind is started over.
+        if let Ok(Some(from)) = destination.xattr(COMPRESSED_FROM) {
+            if from == source.as_bytes() {
+                destination.unlink()?;
+            }
+        }
+
+        // Fails on an existing file or symlink, which isn't ours.
+        let output = destination.create(0o600)?;
+        let format = self.format;
+        let result = copy_into(&metadata, output, |output| {
+            // Without user xattrs, a leftover is only kept, like any other.
+            let _ = xattr::fset(&output, COMPRESSED_FROM, source.as_bytes());
+            match format {
+                Compression::Gzip => {
+                    let mut encoder =
+                        flate2::write::GzEncoder::new(output, flate2::Compression::default());
+                    io::copy(&mut input, &mut encoder)?;
+                    encoder.finish()
+                }
+                Compression::Zstd => {
+                    let mut encoder = zstd::stream::Encoder::new(output, 0)?;
+                    io::copy(&mut input, &mut encoder)?;
+                    encoder.finish()
+                }
+            }
+        });
+        let compressed = match result {
+            Ok(compressed) => compressed,
+            Err(e) => {
+                let _ = destination.unlink();
+                return Err(e);
+            }
+        };
+        target.unlink()?;
+
+        Ok(Outcome {
+            bytes_freed: unlinked_bytes(&metadata).saturating_sub(compressed),
+            ..Outcome::default()
+        })
+    }
+}
+
+/// Removes every permission bit, leaving the file in place.
+pub struct Chmod;
+
+impl Action for Chmod {
+    fn spec(&self) -> String {
+        "chmod".to_string()
+    }
+
+    fn apply(&self, target: &Target) -> io::Result<Outcome> {
+        let (file, _) = open_regular(target, libc::O_RDONLY)?;
+        file.set_permissions(Permissions::from_mode(0o000))?;
+        Ok(Outcome::default())
+    }
+}
+
+/// Appends the file to the tar archive at `archive`, creating it if
+/// needed, and then removes it. The archive is rewritten next to itself and
+/// renamed over the previous one once synced, so an interruption leaves the
+/// latter as it was. A new archive is only readable by root.
+pub struct Archive {
+    pub archive: PathBuf,
+}
+
+impl Action for Archive {
+    fn spec(&self) -> String {
+        format!("archive:{}", self.archive.display())
+    }
+
+    fn apply(&self, target: &Target) -> io::Result<Outcome> {
+        let (mut input, _) = open_regular(target, libc::O_RDONLY)?;
+        let archive = Target::open(&self.archive)?;
+        let partial = archive.sibling(".partial")?;
+        // Left behind by an interrupted attempt.
+        match partial.unlink() {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+
+        let mut output = partial.create(0o600)?;
+        let result = (|| {
+            match open_regular(&archive, libc::O_RDONLY) {
+                // Everything but the trailer, `finish` writes a new one after
+                // the appended entry.
+                Ok((existing, metadata)) => {
+                    fchown(&output, Some(metadata.uid()), Some(metadata.gid()))?;
+                    output.set_permissions(metadata.permissions())?;
+                    let len = metadata.len().saturating_sub(TAR_TRAILER_LEN);
+                    io::copy(&mut existing.take(len), &mut output)?;
+                }
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e),
+            }
+
+            let path = target.path();
+            let name = path.strip_prefix("/").unwrap_or(path);
+            let mut builder = tar::Builder::new(output);
+            builder.append_file(name, &mut input)?;
+            builder.into_inner()?.sync_all()?;
+            partial.rename_over(&archive)
+        })();
+        if let Err(e) = result {
+            let _ = partial.unlink();
+            return Err(e);
+        }
+
+        target.unlink()?;
+        Ok(Outcome::default())
+    }
+}
+
+/// Overwrites the contents of the file `passes` times with random data,
+/// syncing after each pass, deallocates its blocks and then unlinks it.
+/// Files with other hard links are refused unless `allow_links` is set, as
+/// their contents would still be reachable under another name.
+pub struct Shred {
+    pub passes: u32,
+    pub allow_links: bool,
+}
+
+impl Action for Shred {
+    fn spec(&self) -> String {
+        if self.allow_links {
+            format!("shred:{},hardlinks", self.passes)
+        } else {
+            format!("shred:{}", self.passes)
+        }
+    }
+
+    fn apply(&self, target: &Target) -> io::Result<Outcome> {
+        let (mut file, metadata) = open_regular(target, libc::O_WRONLY)?;
+        if metadata.nlink() > 1 && !self.allow_links {
+            return Err(io::Error::other(format!(
+                "refusing to shred, the file has {} hard links",
+                metadata.nlink()
+            )));
+        }
+
+        let len = metadata.len();
+        let mut random = File::open("/dev/urandom")?;
+        let mut buffer = vec![0u8; SHRED_BUFFER_LEN];
+        for _ in 0..self.passes {
+            file.seek(SeekFrom::Start(0))?;
+            let mut written = 0;
+            while written < len {
+                let chunk = (len - written).min(buffer.len() as u64) as usize;
+                random.read_exact(&mut buffer[..chunk])?;
+                file.write_all(&buffer[..chunk])?;
+                written += chunk as u64;
+            }
+            file.sync_all()?;
+        }
+
+        // Give the blocks back to the device, which matters for thinly
+        // provisioned storage and SSDs. Not every filesystem supports it.
+        if len > 0 {
+            let ret = unsafe {
+                libc::fallocate(
+                    file.as_raw_fd(),
+                    libc::FALLOC_FL_PUNCH_HOLE | libc::FALLOC_FL_KEEP_SIZE,
+                    0,
+                    len as libc::off_t,
+                )
+            };
+            if ret != 0 {
+                let err = io::Error::last_os_error();
+                if !matches!(
+                    err.raw_os_error(),
+                    Some(libc::EOPNOTSUPP) | Some(libc::ENOSYS)
+                ) {
+                    return Err(err);
+                }
+            }
+            file.sync_all()?;
+        }
+
+        target.unlink()?;
+        Ok(Outcome {
+            bytes_freed: len,
+            ..Outcome::default()
+        })
+    }
+}
+
+fn parse_shred(argument: Option<&str>) -> Result<Shred, String> {
+    let mut shred = Shred {
+        passes: DEFAULT_SHRED_PASSES,
+        allow_links: false,
+    };
+    for option in argument.into_iter().flat_map(|a| a.split(',')) {
+        match option.trim() {
+            "hardlinks" => shred.allow_links = true,
+            passes => {
+                shred.passes = passes
+                    .parse()
+                    .ok()
+                    .filter(|passes| *passes > 0)
+                    .ok_or_else(|| format!("action `shred`: invalid option `{passes}`"))?;
+            }
+        }
+    }
+    Ok(shred)
+}
+
+/// The kind of action of a spec, e.g. `trash` for `trash:/srv/trash`.
+pub fn kind(spec: &str) -> &str {
+    spec.trim()
+        .split_once(':')
+        .map_or(spec.trim(), |(kind, _)| kind)
+}
+
+/// Parses an action spec: `delete`, `quarantine[:<grace>]`, `trash:<dir>`,
+/// `truncate`, `gzip`, `zstd`, `chmod`, `archive:<file.tar>` or
+/// `shred[:<passes>][,hardlinks]`.
+pub fn parse_action(spec: &str) -> Result<Box<dyn Action>, String> {
+    let (kind, argument) = match spec.trim().split_once(':') {
+        Some((kind, argument)) => (kind, Some(argument)),
+        None => (spec.trim(), None),
+    };
+
+    let absolute = |argument: Option<&str>| match argument {
+        Some(path) if Path::new(path).is_absolute() => Ok(PathBuf::from(path)),
+        Some(path) => Err(format!("action `{kind}`: `{path}` must be absolute")),
+        None => Err(format!(
+            "action `{kind}` requires a path, e.g. `{kind}:/path`"
+        )),
+    };
+    let no_argument = |action: Box<dyn Action>| match argument {
+        Some(_) => Err(format!("action `{kind}` doesn't take an argument")),
+        None => Ok(action),
+    };
+
+    match kind {
+        "delete" => no_argument(Box::new(Delete)),
+        "truncate" => no_argument(Box::new(Truncate)),
+        "chmod" => no_argument(Box::new(Chmod)),
+        "gzip" => no_argument(Box::new(Compress {
+            format: Compression::Gzip,
+        })),
+        "zstd" => no_argument(Box::new(Compress {
+            format: Compression::Zstd,
+        })),
+        "quarantine" => Ok(Box::new(Quarantine {
+            grace: match argument {
+                Some(grace) => parse_age(grace).map_err(|e| format!("action `{kind}`: {e}"))?,
+                None => DEFAULT_GRACE,
+            },
+        })),
+        "shred" => Ok(Box::new(parse_shred(argument)?)),
+        "trash" => Ok(Box::new(Trash {
+            dir: absolute(argument)?,
+        })),
+        "archive" => Ok(Box::new(Archive {
+            archive: absolute(argument)?,
+        })),
+        kind => Err(format!("unknown action `{kind}`")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+    use tempfile::TempDir;
+
+    fn apply(spec: &str, path: &Path) -> io::Result<Outcome> {
+        parse_action(spec)
+            .unwrap()
+            .apply(&Target::open(path).unwrap())
+    }
+
+    #[test]
+    fn deletes() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("bye");
+        fs::write(&path, b"bye").unwrap();
+
+        let outcome = apply("delete", &path).unwrap();
+
+        assert!(!path.exists());
+        assert_eq!(outcome.bytes_freed, 3);
+    }
+
+    #[test]
+    fn truncates() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("log");
+        fs::write(&path, b"lines").unwrap();
+
+        let outcome = apply("truncate", &path).unwrap();
+
+        assert_eq!(fs::metadata(&path).unwrap().len(), 0);
+        assert_eq!(outcome.bytes_freed, 5);
+    }
+
+    #[test]
+    fn removes_every_permission() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("secret");
+        fs::write(&path, b"").unwrap();
+
+        apply("chmod", &path).unwrap();
+
+        assert_eq!(fs::metadata(&path).unwrap().mode() & 0o7777, 0);
+    }
+
+    #[test]
+    fn shreds() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("secret");
+        fs::write(&path, b"secret").unwrap();
+
+        let outcome = apply("shred:1", &path).unwrap();
+
+        assert!(fs::symlink_metadata(&path).is_err());
+        assert_eq!(outcome.bytes_freed, 6);
+    }
+
+    #[test]
+    fn shreds_hard_links_only_when_told_to() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("secret");
+        let link = tmp.path().join("link");
+        let contents = b"secret ".repeat(100);
+        fs::write(&path, &contents).unwrap();
+        fs::hard_link(&path, &link).unwrap();
+
+        assert!(apply("shred", &path).is_err());
+        assert_eq!(fs::read(&path).unwrap(), contents);
+
+        apply("shred:2,hardlinks", &path).unwrap();
+        assert!(fs::symlink_metadata(&path).is_err());
+        // Whatever is left under the other name has been overwritten.
+        assert_ne!(fs::read(&link).unwrap(), contents);
+    }
+
+    #[test]
+    fn parses_shred_options() {
+        for (spec, parsed) in [
+            ("shred", "shred:3"),
+            ("shred:5", "shred:5"),
+            ("shred:hardlinks", "shred:3,hardlinks"),
+            ("shred:5,hardlinks", "shred:5,hardlinks"),
+            ("shred: hardlinks , 2", "shred:2,hardlinks"),
+        ] {
+            assert_eq!(parse_action(spec).unwrap().spec(), parsed, "{spec}");
+        }
+        for spec in ["shred:0", "shred:-1", "shred:many", "shred:2,"] {
+            assert!(parse_action(spec).is_err(), "{spec}");
+        }
+    }
+
+    #[test]
+    fn compresses() {
+        let tmp = TempDir::new().unwrap();
+        let contents = b"compress me ".repeat(100);
+        for (spec, extension) in [("gzip", "gz"), ("zstd", "zst")] {
+            let path = tmp.path().join(spec);
+            fs::write(&path, &contents).unwrap();
+            fs::set_permissions(&path, Permissions::from_mode(0o640)).unwrap();
+
+            let outcome = apply(spec, &path).unwrap();
+
+            let compressed = path.with_extension(extension);
+            let mut decompressed = Vec::new();
+            let file = File::open(&compressed).unwrap();
+            match spec {
+                "gzip" => flate2::read::GzDecoder::new(file).read_to_end(&mut decompressed),
+                _ => zstd::stream::Decoder::new(file)
+                    .unwrap()
+                    .read_to_end(&mut decompressed),
+            }
+            .unwrap();
+            assert_eq!(decompressed, contents, "{spec}");
+            assert!(!path.exists(), "{spec}");
+            assert_eq!(
+                fs::metadata(&compressed).unwrap().mode() & 0o7777,
+                0o640,
+                "{spec}"
+            );
+            assert!(outcome.bytes_freed > 0, "{spec}");
+        }
+    }
+
+    #[test]
+    fn appends_to_archives() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let archive = dir.join("old.tar");
+        let spec = format!("archive:{}", archive.display());
+        for name in ["first", "second"] {
+            fs::write(dir.join(name), name).unwrap();
+            apply(&spec, &dir.join(name)).unwrap();
+            assert!(!dir.join(name).exists());
+        }
+
+        let mut entries = Vec::new();
+        for entry in tar::Archive::new(File::open(&archive).unwrap())
+            .entries()
+            .unwrap()
+        {
+            let mut entry = entry.unwrap();
+            let mut contents = String::new();
+            entry.read_to_string(&mut contents).unwrap();
+            entries.push((entry.path().unwrap().into_owned(), contents));
+        }
+        let relative = dir.strip_prefix("/").unwrap();
+        assert_eq!(
+            entries,
+            [
+                (relative.join("first"), "first".to_string()),
+                (relative.join("second"), "second".to_string()),
+            ]
+        );
+        assert_eq!(fs::metadata(&archive).unwrap().mode() & 0o7777, 0o600);
+        assert!(!dir.join("old.tar.partial").exists());
+    }
+
+    #[test]
+    fn refuses_symlinks() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let target = dir.join("target");
+        fs::write(&target, b"keep").unwrap();
+        fs::set_permissions(&target, Permissions::from_mode(0o644)).unwrap();
+        let link = dir.join("link");
+        symlink(&target, &link).unwrap();
+
+        let archive = format!("archive:{}", dir.join("a.tar").display());
+        for spec in ["truncate", "chmod", "gzip", "zstd", "shred", &archive] {
+            assert!(apply(spec, &link).is_err(), "{spec}");
+        }
+        // Deleting the link leaves what it points to alone.
+        apply("delete", &link).unwrap();
+
+        assert!(fs::symlink_metadata(&link).is_err());
+        assert_eq!(fs::read(&target).unwrap(), b"keep");
+        assert_eq!(fs::metadata(&target).unwrap().mode() & 0o7777, 0o644);
+        assert!(!dir.join("link.gz").exists());
+        assert!(!dir.join("a.tar").exists());
+    }
+}
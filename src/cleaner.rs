@@ -0,0 +1,1037 @@
+use crate::action::{self, parse_action, Action, Outcome, DEFAULT_ACTION};
+use crate::config::Config;
+use crate::error::SweeperError;
+use crate::escape::escape;
+use crate::log::Fields;
+use crate::quarantine;
+use crate::retry::{self, Class};
+use crate::rules::Rule;
+use crate::state::State;
+use crate::store::{self, epoch, Entry, Quarantined, Store, Update};
+use crate::target::Target;
+use crate::xattr;
+use crate::{error, info, warn};
+use std::ffi::OsStr;
+use std::fs;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::MetadataExt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// Rows scheduled by a rule store this prefix followed by the rule name in
+// the `name` column, instead of the xattr name.
+pub const RULE_PREFIX: &str = "rule:";
+
+// Error of the entries a dry run skipped instead of expiring them.
+const DRY_RUN: &str = "dry-run";
+
+// How long the cleaner waits after a pass the store failed.
+const STORE_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+// The config and the rules are replaced as a whole on reload, so a sweep
+// keeps the ones it started with.
+pub type Shared<T> = Arc<RwLock<Arc<T>>>;
+
+pub fn shared<T>(value: T) -> Shared<T> {
+    Arc::new(RwLock::new(Arc::new(value)))
+}
+
+/// The current value, which stays valid however long it's held.
+pub fn current<T>(shared: &Shared<T>) -> Arc<T> {
+    shared.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+pub fn replace<T>(shared: &Shared<T>, value: T) {
+    *shared.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(value);
+}
+
+/// Picks what to do with an expired file: the rule's action for rule-driven
+/// rows, otherwise whatever its `user.expire_action` xattr says, defaulting
+/// to deleting it. Only the actions allowed by the config are, and the xattr
+/// can't pick one writing to a path of its choosing.
+fn action_for(
+    config: &Config,
+    target: &Target,
+    rule: Option<&Rule>,
+) -> Result<Box<dyn Action>, SweeperError> {
+    let spec = match rule {
+        Some(rule) => rule.action.clone(),
+        None => match target.xattr(&config.xattr.action) {
+            Ok(Some(value)) => {
+                let spec = String::from_utf8_lossy(&value)
+                    .trim_end_matches('\0')
+                    .trim()
+                    .to_string();
+                if action::PATH_ACTIONS.contains(&action::kind(&spec)) {
+                    return Err(SweeperError::Action(format!(
+                        "action `{}` can only be set by a rule, not by {}",
+                        action::kind(&spec),
+                        config.xattr.action
+                    )));
+                }
+                spec
+            }
+            Ok(None) => DEFAULT_ACTION.to_string(),
+            Err(e) => {
+                return Err(SweeperError::Action(format!(
+                    "reading {}: {}",
+                    config.xattr.action, e
+                )))
+            }
+        },
+    };
+    if !config.allows(&spec) {
+        return Err(SweeperError::Action(format!(
+            "action `{}` isn't allowed",
+            action::kind(&spec)
+        )));
+    }
+    parse_action(&spec).map_err(SweeperError::Action)
+}
+
+/// Makes sure a row still applies: it must be the same file that was
+/// scheduled, and for an xattr-driven row, it must still carry the same
+/// expiration.
+fn verify(thing: &Entry, target: &Target, rule: Option<&Rule>) -> Result<(), String> {
+    let metadata = target.metadata().map_err(|e| e.to_string())?;
+    if let (Some(dev), Some(ino)) = (thing.dev, thing.ino) {
+        if metadata.dev() != dev || metadata.ino() != ino {
+            return Err("the file was replaced since it was scheduled".to_string());
+        }
+    }
+    if rule.is_some() {
+        return Ok(());
+    }
+
+    // The xattr that set it, which is still the one to look at if the
+    // config changed since.
+    let name = &thing.name;
+    let value = target
+        .xattr(name)
+        .map_err(|e| format!("reading {}: {}", name, e))?
+        .ok_or_else(|| format!("{} was removed", name))?;
+    if xattr::parse_expire_at(&value) != Some(thing.expire_at) {
+        return Err(format!(
+            "{} changed to {}",
+            name,
+            escape(OsStr::from_bytes(&value))
+        ));
+    }
+
+    Ok(())
+}
+
+/// What the log says about the expiration of `thing` with `action`. The
+/// latency is how late after its deadline the file got `outcome`.
+fn expiration(thing: &Entry, action: &str, outcome: &str) -> Fields {
+    let deadline = UNIX_EPOCH + Duration::from_secs(thing.expire_at.max(0) as u64);
+    let latency = SystemTime::now()
+        .duration_since(deadline)
+        .unwrap_or_default();
+    vec![
+        ("path", (&thing.path).into()),
+        ("inode", thing.ino.into()),
+        ("uid", thing.uid.into()),
+        ("pid", thing.pid.into()),
+        ("comm", thing.comm.as_deref().into()),
+        ("deadline", thing.expire_at.into()),
+        ("action", action.into()),
+        ("outcome", outcome.into()),
+        ("latency", latency.into()),
+    ]
+}
+
+/// Why a file is being expired, for the dry-run report.
+fn reason(thing: &Entry, rule: Option<&Rule>) -> String {
+    match rule {
+        Some(rule) => format!(
+            "rule `{}`: {:?} older than {}s",
+            rule.name,
+            rule.ages,
+            rule.max_age.as_secs()
+        ),
+        None => format!("{}={}", thing.name, thing.expire_at),
+    }
+}
+
+fn record_dry_run(store: &dyn Store, thing: &Entry, spec: &str, reason: &str) -> store::Result<()> {
+    let size = fs::symlink_metadata(&thing.path).ok().map(|m| m.len());
+    store.record_dry_run(&thing.path, &thing.name, spec, size, reason)
+}
+
+/// The id of an entry read from the store, which always has one.
+fn id(thing: &Entry) -> store::Result<i64> {
+    thing
+        .id
+        .ok_or_else(|| format!("no id for the entry of {}", escape(&thing.path)).into())
+}
+
+/// Skips an entry that no longer applies because of `error`.
+fn skip(store: &dyn Store, thing: &Entry, error: &str, dry_run: bool) -> store::Result<()> {
+    info!(
+        expiration(thing, "skip", "skipped") =>
+        "╰ 😴 Skipping {}: {}", escape(&thing.path), error
+    );
+    if dry_run {
+        record_dry_run(store, thing, "skip", error)?;
+    }
+    store.transition(
+        id(thing)?,
+        State::Pending,
+        State::Skipped,
+        Update {
+            error: Some(error),
+            ..Update::default()
+        },
+    )?;
+    Ok(())
+}
+
+/// Fails only if the store does, failed actions are recorded on the entry.
+fn expire(
+    store: &dyn Store,
+    config: &Config,
+    thing: &Entry,
+    target: &Target,
+    rule: Option<&Rule>,
+    dry_run: bool,
+) -> store::Result<()> {
+    let id = id(thing)?;
+    let path = &thing.path;
+    let dry_run = dry_run || rule.is_some_and(|rule| rule.dry_run);
+
+    if let Err(e) = verify(thing, target, rule) {
+        return skip(store, thing, &e, dry_run);
+    }
+
+    let action = action_for(config, target, rule);
+    let spec = match &action {
+        Ok(action) => action.spec(),
+        Err(_) => "unknown".to_string(),
+    };
+
+    if dry_run {
+        let reason = match &action {
+            Ok(_) => reason(thing, rule),
+            Err(e) => e.to_string(),
+        };
+        info!(
+            expiration(thing, &spec, "dry-run") =>
+            "🧪 Would expire {} with `{}` ({})",
+            escape(path),
+            spec,
+            reason
+        );
+        record_dry_run(store, thing, &spec, &reason)?;
+        store.transition(
+            id,
+            State::Pending,
+            State::Skipped,
+            Update {
+                error: Some(DRY_RUN),
+                ..Update::default()
+            },
+        )?;
+        return Ok(());
+    }
+
+    // Two-phase: the entry is marked as `deleting` before touching the file,
+    // so if we crash halfway through, `recover` finds it on the next start.
+    let marked = store.transition(
+        id,
+        State::Pending,
+        State::Deleting,
+        Update {
+            action: Some(&spec),
+            ..Update::default()
+        },
+    )?;
+    if !marked {
+        // It was cancelled in the meantime.
+        return Ok(());
+    }
+
+    let result = match action {
+        Ok(action) => {
+            if spec == DEFAULT_ACTION {
+                info!(expiration(thing, &spec, "expiring") => "🚮 Deleting {}", escape(path));
+            } else {
+                info!(
+                    expiration(thing, &spec, "expiring") =>
+                    "🚮 Expiring {} with `{}`", escape(path), spec
+                );
+            }
+            apply(store, action.as_ref(), target)
+                .map_err(|e| (retry::classify(&e, target), e.to_string()))
+        }
+        Err(e) => Err((Class::Permanent, e.to_string())),
+    };
+    match result {
+        Ok(outcome) => {
+            info!(
+                expiration(thing, &spec, "deleted") =>
+                "╰ ✅ Freed {} byte(s)", outcome.bytes_freed
+            );
+            store.transition(
+                id,
+                State::Deleting,
+                State::Deleted,
+                Update {
+                    bytes_freed: Some(outcome.bytes_freed),
+                    ..Update::default()
+                },
+            )?;
+        }
+        Err((class, error)) => fail(store, config, thing, &spec, class, &error)?,
+    }
+    Ok(())
+}
+
+/// Applies `action` to `target`. Where it moves the file to is recorded
+/// first, so an interruption can't leave it in the trash with nobody knowing.
+fn apply(store: &dyn Store, action: &dyn Action, target: &Target) -> io::Result<Outcome> {
+    let Some((destination, grace)) = action.destination(target)? else {
+        return action.apply(target);
+    };
+    let quarantine_id = quarantine::record(store, target, &destination, grace)
+        .map_err(|e| io::Error::other(e.to_string()))?;
+
+    let path = target.path();
+    match action.apply_to(target, &destination) {
+        Ok(outcome) => {
+            info!(
+                path = path, moved_to = destination.as_path();
+                "╰ 🗑  Quarantined as #{} in {}, restore with `sweeper restore {}`",
+                quarantine_id,
+                escape(&destination),
+                quarantine_id
+            );
+            Ok(outcome)
+        }
+        Err(e) => {
+            if let Err(e) = store.forget_quarantined(quarantine_id) {
+                // Harmless, there's nothing to purge or restore.
+                warn!(
+                    path = path;
+                    "╰ ⚠️  Couldn't forget quarantine #{}: {}", quarantine_id, e
+                );
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Handles an action that failed: transient errors are retried with an
+/// exponential backoff until `max_attempts` is reached, anything else parks
+/// the entry in `failed` for someone to look at.
+fn fail(
+    store: &dyn Store,
+    config: &Config,
+    thing: &Entry,
+    spec: &str,
+    class: Class,
+    error: &str,
+) -> store::Result<()> {
+    let max_attempts = config.limits.max_attempts;
+    let id = id(thing)?;
+    let attempts = thing.attempts + 1;
+
+    let (to, next_attempt_at) = match class {
+        Class::Vanished => {
+            info!(
+                expiration(thing, spec, "vanished") =>
+                "╰ 👻 {} vanished in the meantime", escape(&thing.path)
+            );
+            (State::Skipped, None)
+        }
+        Class::Transient if attempts < max_attempts => {
+            let delay = retry::backoff(attempts);
+            warn!(
+                expiration(thing, spec, "retrying") =>
+                "╰ ⏳ {} (attempt {}/{}), retrying in {}s",
+                error,
+                attempts,
+                max_attempts,
+                delay.as_secs()
+            );
+            (State::Pending, Some(epoch(SystemTime::now() + delay)))
+        }
+        _ => {
+            error!(
+                expiration(thing, spec, "failed") =>
+                "╰ ❌ {} (attempt {}), giving up", error, attempts
+            );
+            (State::Failed, None)
+        }
+    };
+
+    store.transition(
+        id,
+        State::Deleting,
+        to,
+        Update {
+            error: Some(error),
+            attempts: Some(attempts),
+            next_attempt_at,
+            ..Update::default()
+        },
+    )?;
+    Ok(())
+}
+
+fn schedule_rules(store: &dyn Store, rules: &[Rule]) -> store::Result<()> {
+    for rule in rules {
+        let name = format!("{}{}", RULE_PREFIX, rule.name);
+        for (path, deadline, metadata) in rule.scan() {
+            store.schedule(
+                &path,
+                &name,
+                epoch(deadline),
+                metadata.dev(),
+                metadata.ino(),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Rows scheduled by a rule are checked again right before acting on them,
+/// as the file might have been modified since the last scan. Returns the
+/// rule if the file is still due.
+fn recheck<'a>(
+    store: &dyn Store,
+    rules: &'a [Rule],
+    rule_name: &str,
+    thing: &Entry,
+    target: &Target,
+) -> store::Result<Option<&'a Rule>> {
+    let rule = rules.iter().find(|rule| rule.name == rule_name);
+    let metadata = target.metadata().ok();
+    let deadline = rule
+        .zip(metadata)
+        .and_then(|(rule, metadata)| rule.deadline(&thing.path, &metadata));
+
+    match deadline {
+        Some(deadline) if deadline > SystemTime::now() => {
+            store.reschedule(id(thing)?, epoch(deadline))?;
+            Ok(None)
+        }
+        Some(_) => {
+            info!(
+                path = &thing.path, rule = rule_name;
+                "📏 Rule `{}` matched {}", rule_name, escape(&thing.path)
+            );
+            Ok(rule)
+        }
+        None => {
+            store.transition(
+                id(thing)?,
+                State::Pending,
+                State::Cancelled,
+                Update {
+                    error: Some("no longer covered by the rule"),
+                    ..Update::default()
+                },
+            )?;
+            Ok(None)
+        }
+    }
+}
+
+/// Re-examines the entries left in `deleting` by a previous run that didn't
+/// get to finish them. If the file is gone the action went through, and was
+/// recorded if it moved the file to quarantine; otherwise the entry goes
+/// back to `pending` to be verified and acted upon again, unless the action
+/// can't be redone, then it fails for someone to look at.
+pub fn recover(store: &dyn Store) -> store::Result<()> {
+    for entry in store.entries(State::Deleting)? {
+        let (id, path) = (id(&entry)?, entry.path);
+        let one_shot = entry
+            .action
+            .as_deref()
+            .is_some_and(|spec| action::ONE_SHOT_ACTIONS.contains(&action::kind(spec)));
+        let exists = fs::symlink_metadata(&path).is_ok();
+        if exists && one_shot {
+            error!(
+                path = &path, outcome = "failed";
+                "🩹 Interrupted expiration of {} can't be redone, check it by hand",
+                escape(&path)
+            );
+            store.transition(
+                id,
+                State::Deleting,
+                State::Failed,
+                Update {
+                    error: Some("interrupted, can't be redone safely"),
+                    ..Update::default()
+                },
+            )?;
+        } else if exists {
+            // Quarantined next time, this one never made it to the trash.
+            if let Some(Quarantined {
+                id: Some(quarantine_id),
+                trash_path,
+                ..
+            }) = store.last_quarantined(&path)?
+            {
+                if fs::symlink_metadata(&trash_path).is_err() {
+                    store.forget_quarantined(quarantine_id)?;
+                }
+            }
+            info!(
+                path = &path, outcome = "retrying";
+                "🩹 Retrying interrupted expiration of {}", escape(&path)
+            );
+            store.transition(
+                id,
+                State::Deleting,
+                State::Pending,
+                Update {
+                    error: Some("interrupted, retrying"),
+                    ..Update::default()
+                },
+            )?;
+        } else {
+            info!(
+                path = &path, outcome = "deleted";
+                "🩹 Interrupted expiration of {} had completed",
+                escape(&path)
+            );
+            store.transition(
+                id,
+                State::Deleting,
+                State::Deleted,
+                Update {
+                    error: Some("interrupted, the file was already gone"),
+                    ..Update::default()
+                },
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Puts the entries a dry run skipped back in `pending`, for a run that
+/// isn't one to expire them, unless they were scheduled again since.
+/// Returns how many there were.
+pub fn requeue_dry_runs(store: &dyn Store) -> store::Result<usize> {
+    let mut requeued = 0;
+    for entry in store.entries(State::Skipped)? {
+        if entry.error.as_deref() != Some(DRY_RUN) {
+            continue;
+        }
+        let rescheduled = store
+            .find(&entry.path)?
+            .iter()
+            .any(|other| other.state == State::Pending && other.name == entry.name);
+        if rescheduled {
+            continue;
+        }
+        let moved = store.transition(
+            id(&entry)?,
+            State::Skipped,
+            State::Pending,
+            Update {
+                error: Some("skipped by a dry run"),
+                ..Update::default()
+            },
+        )?;
+        if moved {
+            requeued += 1;
+        }
+    }
+    Ok(requeued)
+}
+
+/// Expires what's due, scheduling what the rules match first if `scan` is
+/// set, and purges the quarantine. Stops between two expirations once
+/// `runnable` is cleared.
+pub fn sweep_once(
+    store: &dyn Store,
+    config: &Config,
+    rules: &[Rule],
+    scan: bool,
+    dry_run: bool,
+    runnable: &AtomicBool,
+) -> store::Result<()> {
+    if scan {
+        schedule_rules(store, rules)?;
+    }
+
+    // Collected upfront, as acting on them writes to the store.
+    let due = store.next_due(config.limits.sweep_batch)?;
+
+    for thing in due {
+        // Stop between expirations, never in the middle of one.
+        if !runnable.load(Ordering::SeqCst) {
+            break;
+        }
+        // One entry failing to be written doesn't stop the others.
+        let result = match Target::open(&thing.path) {
+            Err(e) => skip(store, &thing, &e.to_string(), dry_run),
+            Ok(target) => match thing.name.strip_prefix(RULE_PREFIX) {
+                Some(rule_name) => match recheck(store, rules, rule_name, &thing, &target) {
+                    Ok(Some(rule)) => expire(store, config, &thing, &target, Some(rule), dry_run),
+                    Ok(None) => Ok(()),
+                    Err(e) => Err(e),
+                },
+                None => expire(store, config, &thing, &target, None, dry_run),
+            },
+        };
+        if let Err(e) = result {
+            error!(
+                path = &thing.path;
+                "❌ Couldn't expire {}: {}", escape(&thing.path), e
+            );
+        }
+    }
+
+    // Purging deletes for good, which a dry run mustn't do.
+    if !dry_run {
+        for purged in quarantine::purge(store)? {
+            info!(
+                path = &purged, outcome = "purged";
+                "🔥 Purged {} after its grace period", escape(&purged)
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Runs the cleaner until `runnable` is cleared, with the config and rules
+/// current at the start of each pass. The rules are scanned every
+/// `rules_interval`, or right away when `sweep` is set. A pass the store
+/// fails is logged and counted, and the next one tried a bit later.
+pub fn clean_up(
+    store: &dyn Store,
+    config: &Shared<Config>,
+    rules: &Shared<Vec<Rule>>,
+    sweep: &AtomicBool,
+    dry_run: bool,
+    runnable: &AtomicBool,
+) {
+    let mut last_rules_run: Option<Instant> = None;
+    let mut failed_passes: u64 = 0;
+    let mut retry_at: Option<Instant> = None;
+
+    while runnable.load(Ordering::SeqCst) {
+        if retry_at.is_some_and(|at| Instant::now() < at) {
+            thread::sleep(Duration::from_millis(100));
+            continue;
+        }
+        let config = current(config);
+        let rules = current(rules);
+        let interval = Duration::from_secs(config.limits.rules_interval);
+        let swept = sweep.swap(false, Ordering::SeqCst);
+        let scan = swept || last_rules_run.is_none_or(|last| last.elapsed() >= interval);
+        if scan {
+            last_rules_run = Some(Instant::now());
+        }
+        retry_at = None;
+        if let Err(e) = sweep_once(store, &config, &rules, scan, dry_run, runnable) {
+            failed_passes += 1;
+            error!(
+                failed_passes = failed_passes;
+                "❌ Cleaning up failed ({} time(s) so far), retrying in {}s: {}",
+                failed_passes,
+                STORE_RETRY_DELAY.as_secs(),
+                e
+            );
+            retry_at = Some(Instant::now() + STORE_RETRY_DELAY);
+        }
+
+        thread::sleep(Duration::from_millis(100));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Settings;
+    use crate::store::{now, MemoryStore};
+    use std::io::Read;
+    use std::path::{Path, PathBuf};
+    use tempfile::TempDir;
+
+    fn config() -> Config {
+        Config::from(Settings::default())
+    }
+
+    /// Creates `path` with its `user.expire_at` set, as the daemon would
+    /// record it.
+    fn schedule(store: &dyn Store, path: &Path, expire_at: i64) -> i64 {
+        fs::write(path, b"bye").unwrap();
+        xattr::set(path, xattr::EXPIRE_AT, expire_at.to_string().as_bytes()).unwrap();
+        let metadata = fs::symlink_metadata(path).unwrap();
+        store
+            .insert(&Entry {
+                path: path.to_path_buf(),
+                name: xattr::EXPIRE_AT.to_string(),
+                expire_at,
+                dev: Some(metadata.dev()),
+                ino: Some(metadata.ino()),
+                ..Entry::default()
+            })
+            .unwrap()
+    }
+
+    fn entry(store: &dyn Store, id: i64) -> Entry {
+        store.entry(id).unwrap().unwrap()
+    }
+
+    fn states(store: &dyn Store, id: i64) -> Vec<State> {
+        store
+            .history(id)
+            .unwrap()
+            .into_iter()
+            .map(|transition| transition.state)
+            .collect()
+    }
+
+    fn sweep(store: &dyn Store, dry_run: bool) {
+        let runnable = AtomicBool::new(true);
+        sweep_once(store, &config(), &[], false, dry_run, &runnable).unwrap();
+    }
+
+    #[test]
+    fn expires_only_what_is_due() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let store = MemoryStore::new();
+        let due = schedule(&store, &dir.join("due"), now() - 10);
+        let later = schedule(&store, &dir.join("later"), now() + 3600);
+
+        sweep(&store, false);
+
+        assert_eq!(entry(&store, due).state, State::Deleted);
+        assert!(!dir.join("due").exists());
+        assert_eq!(entry(&store, later).state, State::Pending);
+        assert!(dir.join("later").exists());
+    }
+
+    #[test]
+    fn goes_through_deleting() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let store = MemoryStore::new();
+        let id = schedule(&store, &dir.join("bye"), now() - 10);
+
+        sweep(&store, false);
+
+        assert_eq!(
+            states(&store, id),
+            [State::Pending, State::Deleting, State::Deleted]
+        );
+        assert_eq!(entry(&store, id).action.as_deref(), Some(DEFAULT_ACTION));
+    }
+
+    #[test]
+    fn fails_unknown_actions() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let store = MemoryStore::new();
+        let path = dir.join("bye");
+        let id = schedule(&store, &path, now() - 10);
+        xattr::set(&path, xattr::EXPIRE_ACTION, b"bogus").unwrap();
+
+        sweep(&store, false);
+
+        assert_eq!(
+            states(&store, id),
+            [State::Pending, State::Deleting, State::Failed]
+        );
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn refuses_path_actions_from_the_xattr() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let store = MemoryStore::new();
+        let path = dir.join("bye");
+        let id = schedule(&store, &path, now() - 10);
+        let archive = dir.join("bye.tar");
+        let spec = format!("archive:{}", archive.display());
+        xattr::set(&path, xattr::EXPIRE_ACTION, spec.as_bytes()).unwrap();
+
+        sweep(&store, false);
+
+        assert_eq!(entry(&store, id).state, State::Failed);
+        assert!(path.exists());
+        assert!(!archive.exists());
+    }
+
+    #[test]
+    fn skips_files_whose_expiration_changed() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let store = MemoryStore::new();
+        let path = dir.join("bye");
+        let id = schedule(&store, &path, now() - 10);
+        xattr::set(&path, xattr::EXPIRE_AT, b"4102444800").unwrap();
+
+        sweep(&store, false);
+
+        assert_eq!(entry(&store, id).state, State::Skipped);
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn skips_rule_files_replaced_since_the_scan() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let store = MemoryStore::new();
+        let path = dir.join("old");
+        fs::write(&path, b"scanned").unwrap();
+        let rules = crate::rules::parse_rules(
+            &format!("[[rule]]\npath = \"{}\"\nmax_age = \"0\"", dir.display()),
+            Path::new("test.toml"),
+        )
+        .unwrap();
+        schedule_rules(&store, &rules).unwrap();
+        fs::write(dir.join("new"), b"swapped in").unwrap();
+        fs::rename(dir.join("new"), &path).unwrap();
+
+        let runnable = AtomicBool::new(true);
+        sweep_once(&store, &config(), &rules, false, false, &runnable).unwrap();
+
+        let skipped = store.entries(State::Skipped).unwrap();
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(fs::read(&path).unwrap(), b"swapped in");
+    }
+
+    #[test]
+    fn skips_files_whose_directory_became_a_symlink() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let store = MemoryStore::new();
+        fs::create_dir(dir.join("sub")).unwrap();
+        fs::create_dir(dir.join("elsewhere")).unwrap();
+        let id = schedule(&store, &dir.join("sub/bye"), now() - 10);
+        fs::rename(dir.join("sub/bye"), dir.join("elsewhere/bye")).unwrap();
+        fs::remove_dir(dir.join("sub")).unwrap();
+        std::os::unix::fs::symlink(dir.join("elsewhere"), dir.join("sub")).unwrap();
+
+        sweep(&store, false);
+
+        assert_eq!(entry(&store, id).state, State::Skipped);
+        assert!(dir.join("elsewhere/bye").exists());
+    }
+
+    #[test]
+    fn leaves_files_alone_in_dry_run() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let store = MemoryStore::new();
+        let path = dir.join("bye");
+        let id = schedule(&store, &path, now() - 10);
+
+        sweep(&store, true);
+
+        assert_eq!(entry(&store, id).state, State::Skipped);
+        assert!(path.exists());
+        assert_eq!(store.dry_runs().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn expires_what_a_dry_run_skipped_once_it_is_over() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let store = MemoryStore::new();
+        let skipped = schedule(&store, &dir.join("skipped"), now() - 10);
+        let rescheduled = schedule(&store, &dir.join("rescheduled"), now() - 10);
+        sweep(&store, true);
+        let newer = schedule(&store, &dir.join("rescheduled"), now() + 3600);
+
+        assert_eq!(requeue_dry_runs(&store).unwrap(), 1);
+        sweep(&store, false);
+
+        assert_eq!(entry(&store, skipped).state, State::Deleted);
+        assert!(!dir.join("skipped").exists());
+        assert_eq!(entry(&store, rescheduled).state, State::Skipped);
+        assert_eq!(entry(&store, newer).state, State::Pending);
+        assert!(dir.join("rescheduled").exists());
+    }
+
+    #[test]
+    fn retries_transient_failures_with_backoff() {
+        let config = config();
+        let store = MemoryStore::new();
+        let id = store
+            .insert(&Entry {
+                path: PathBuf::from("/nonexistent/bye"),
+                name: xattr::EXPIRE_AT.to_string(),
+                expire_at: now() - 10,
+                ..Entry::default()
+            })
+            .unwrap();
+
+        for attempt in 1..config.limits.max_attempts {
+            let thing = entry(&store, id);
+            store
+                .transition(id, State::Pending, State::Deleting, Update::default())
+                .unwrap();
+            let before = now();
+            fail(&store, &config, &thing, "delete", Class::Transient, "busy").unwrap();
+
+            let thing = entry(&store, id);
+            assert_eq!(thing.state, State::Pending);
+            assert_eq!(thing.attempts, attempt);
+            let delay = retry::backoff(attempt).as_secs() as i64;
+            let next = thing.next_attempt_at.unwrap();
+            assert!((before + delay..=now() + delay).contains(&next));
+            // Not due again until then.
+            assert!(store.next_due(10).unwrap().is_empty());
+        }
+
+        let thing = entry(&store, id);
+        store
+            .transition(id, State::Pending, State::Deleting, Update::default())
+            .unwrap();
+        fail(&store, &config, &thing, "delete", Class::Transient, "busy").unwrap();
+        let thing = entry(&store, id);
+        assert_eq!(thing.state, State::Failed);
+        assert_eq!(thing.attempts, config.limits.max_attempts);
+        assert_eq!(thing.next_attempt_at, None);
+    }
+
+    #[test]
+    fn fails_permanent_errors_right_away() {
+        let store = MemoryStore::new();
+        let id = store
+            .insert(&Entry {
+                path: PathBuf::from("/nonexistent/bye"),
+                expire_at: now() - 10,
+                ..Entry::default()
+            })
+            .unwrap();
+        let thing = entry(&store, id);
+        store
+            .transition(id, State::Pending, State::Deleting, Update::default())
+            .unwrap();
+        fail(&store, &config(), &thing, "delete", Class::Permanent, "no").unwrap();
+
+        let thing = entry(&store, id);
+        assert_eq!(thing.state, State::Failed);
+        assert_eq!(thing.attempts, 1);
+    }
+
+    #[test]
+    fn recovers_interrupted_expirations() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let store = MemoryStore::new();
+        let kept = schedule(&store, &dir.join("kept"), now() - 10);
+        let gone = schedule(&store, &dir.join("gone"), now() - 10);
+        for id in [kept, gone] {
+            store
+                .transition(id, State::Pending, State::Deleting, Update::default())
+                .unwrap();
+        }
+        fs::remove_file(dir.join("gone")).unwrap();
+
+        recover(&store).unwrap();
+
+        assert_eq!(entry(&store, kept).state, State::Pending);
+        assert_eq!(entry(&store, gone).state, State::Deleted);
+        // And the interrupted one goes through on the next sweep.
+        sweep(&store, false);
+        assert_eq!(entry(&store, kept).state, State::Deleted);
+        assert!(!dir.join("kept").exists());
+    }
+
+    #[test]
+    fn fails_interrupted_archives_rather_than_appending_twice() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let store = MemoryStore::new();
+        let path = dir.join("bye");
+        let id = schedule(&store, &path, now() - 10);
+        let spec = format!("archive:{}", dir.join("old.tar").display());
+        store
+            .transition(
+                id,
+                State::Pending,
+                State::Deleting,
+                Update {
+                    action: Some(&spec),
+                    ..Update::default()
+                },
+            )
+            .unwrap();
+
+        recover(&store).unwrap();
+
+        assert_eq!(entry(&store, id).state, State::Failed);
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn forgets_quarantines_that_were_interrupted_before_the_move() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let store = MemoryStore::new();
+        let path = dir.join("bye");
+        let id = schedule(&store, &path, now() - 10);
+        store
+            .transition(id, State::Pending, State::Deleting, Update::default())
+            .unwrap();
+        quarantine::record(
+            &store,
+            &Target::open(&path).unwrap(),
+            &dir.join("never-moved"),
+            Duration::ZERO,
+        )
+        .unwrap();
+
+        recover(&store).unwrap();
+
+        assert_eq!(entry(&store, id).state, State::Pending);
+        assert!(store.last_quarantined(&path).unwrap().is_none());
+    }
+
+    #[test]
+    fn redoes_compressions_that_were_interrupted() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let store = MemoryStore::new();
+        let path = dir.join("bye");
+        let id = schedule(&store, &path, now() - 10);
+        xattr::set(&path, xattr::EXPIRE_ACTION, b"gzip").unwrap();
+        let metadata = fs::metadata(&path).unwrap();
+        let partial = dir.join("bye.gz");
+        fs::write(&partial, b"half").unwrap();
+        let source = format!("{}:{}", metadata.dev(), metadata.ino());
+        xattr::set(&partial, action::COMPRESSED_FROM, source.as_bytes()).unwrap();
+
+        sweep(&store, false);
+
+        assert_eq!(entry(&store, id).state, State::Deleted);
+        assert!(!path.exists());
+        let mut decoded = Vec::new();
+        flate2::read::GzDecoder::new(fs::File::open(&partial).unwrap())
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, b"bye");
+    }
+
+    #[test]
+    fn leaves_compressed_files_of_others_alone() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let store = MemoryStore::new();
+        let path = dir.join("bye");
+        let id = schedule(&store, &path, now() - 10);
+        xattr::set(&path, xattr::EXPIRE_ACTION, b"gzip").unwrap();
+        fs::write(dir.join("bye.gz"), b"theirs").unwrap();
+
+        sweep(&store, false);
+
+        assert_eq!(entry(&store, id).state, State::Failed);
+        assert!(path.exists());
+        assert_eq!(fs::read(dir.join("bye.gz")).unwrap(), b"theirs");
+    }
+}
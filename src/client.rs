@@ -0,0 +1,473 @@
+use crate::config::{load_config, Config, CONFIG_PATH};
+use crate::control::{
+    Body, Cancelled, Command, Extended, Notification, Reloaded, Request, Response, Shown,
+    PROTOCOL_VERSION,
+};
+use crate::escape::escape;
+use crate::event::MAX_PATH_LEN;
+use crate::state::State;
+use crate::store::Entry;
+use crate::xattr;
+use serde::de::DeserializeOwned;
+use std::ffi::OsStr;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// How often `Client::confirm` asks the daemon.
+const CONFIRM_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// The daemon would ignore the expiration, or get it wrong.
+    Invalid(String),
+    /// Setting the xattr, or talking to the daemon.
+    Io(io::Error),
+    /// The daemon turned the request down.
+    Daemon(String),
+    /// The daemon answered something we don't understand.
+    Protocol(String),
+    /// The daemon didn't register the expiration in time.
+    Timeout,
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClientError::Invalid(e) => f.write_str(e),
+            ClientError::Io(e) => e.fmt(f),
+            ClientError::Daemon(e) => write!(f, "daemon: {}", e),
+            ClientError::Protocol(e) => write!(f, "protocol: {}", e),
+            ClientError::Timeout => f.write_str("the daemon didn't register the expiration"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ClientError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ClientError {
+    fn from(e: io::Error) -> Self {
+        ClientError::Io(e)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+/// Makes `path` absolute, as the daemon ignores relative ones, and checks
+/// the BPF program can read it in full.
+fn absolute(path: &Path) -> Result<PathBuf> {
+    let path = std::path::absolute(path)?;
+    if path.as_os_str().len() > MAX_PATH_LEN {
+        return Err(ClientError::Invalid(format!(
+            "the path is longer than the {} bytes the daemon can see",
+            MAX_PATH_LEN
+        )));
+    }
+    Ok(path)
+}
+
+/// The deadline in whole seconds, rounded up so a file never expires
+/// before it was asked to.
+fn timestamp(at: SystemTime) -> Result<i64> {
+    let since = at
+        .duration_since(UNIX_EPOCH)
+        .map_err(|_| ClientError::Invalid("expiration before the epoch".to_string()))?;
+    let secs = since.as_secs() + u64::from(since.subsec_nanos() > 0);
+    i64::try_from(secs).map_err(|_| ClientError::Invalid("expiration too far away".to_string()))
+}
+
+/// The config the daemon runs with unless told otherwise, from the file at
+/// `CONFIG_PATH` if there is one.
+pub fn default_config() -> Result<Config> {
+    let settings = load_config(Path::new(CONFIG_PATH), false).map_err(ClientError::Invalid)?;
+    Ok(Config::from(settings))
+}
+
+/// The xattr the daemon watches by default, `user.expire_at` unless its
+/// config file says otherwise.
+pub fn watched_xattr() -> Result<String> {
+    Ok(default_config()?.xattr.expire_at)
+}
+
+/// Makes `path` expire at `at` by setting the xattr the daemon watches,
+/// which picks it up. Returns the timestamp it was set to.
+pub fn expire_at(path: &Path, at: SystemTime) -> Result<i64> {
+    expire_at_with_name(path, &watched_xattr()?, at)
+}
+
+/// Like [`expire_at`], for a daemon watching the `name` xattr.
+pub fn expire_at_with_name(path: &Path, name: &str, at: SystemTime) -> Result<i64> {
+    let path = absolute(path)?;
+    let expire_at = timestamp(at)?;
+    xattr::set(&path, name, expire_at.to_string().as_bytes())?;
+    Ok(expire_at)
+}
+
+/// Makes `path` expire once `after` has passed.
+pub fn expire_in(path: &Path, after: Duration) -> Result<i64> {
+    expire_in_with_name(path, &watched_xattr()?, after)
+}
+
+/// Like [`expire_in`], for a daemon watching the `name` xattr.
+pub fn expire_in_with_name(path: &Path, name: &str, after: Duration) -> Result<i64> {
+    let at = SystemTime::now()
+        .checked_add(after)
+        .ok_or_else(|| ClientError::Invalid("expiration too far away".to_string()))?;
+    expire_at_with_name(path, name, at)
+}
+
+/// When `path` expires according to the xattr the daemon watches, read the
+/// way the daemon reads it. A value it can't make sense of is an error.
+pub fn expiration(path: &Path) -> Result<Option<SystemTime>> {
+    expiration_with_name(path, &watched_xattr()?)
+}
+
+/// Like [`expiration`], for a daemon watching the `name` xattr.
+pub fn expiration_with_name(path: &Path, name: &str) -> Result<Option<SystemTime>> {
+    let Some(value) = xattr::get(path, name)? else {
+        return Ok(None);
+    };
+    let expire_at = xattr::parse_expire_at(&value).ok_or_else(|| {
+        ClientError::Invalid(format!(
+            "{} isn't a timestamp: {}",
+            name,
+            escape(OsStr::from_bytes(&value))
+        ))
+    })?;
+    Ok(Some(match u64::try_from(expire_at) {
+        Ok(secs) => UNIX_EPOCH + Duration::from_secs(secs),
+        Err(_) => UNIX_EPOCH - Duration::from_secs(expire_at.unsigned_abs()),
+    }))
+}
+
+/// Removes the xattr the daemon watches from `path`. The daemon skips the
+/// file when its expiration comes. Returns whether it had one.
+pub fn clear(path: &Path) -> Result<bool> {
+    clear_with_name(path, &watched_xattr()?)
+}
+
+/// Like [`clear`], for a daemon watching the `name` xattr.
+pub fn clear_with_name(path: &Path, name: &str) -> Result<bool> {
+    Ok(xattr::remove(path, name)?)
+}
+
+/// A connection to the control socket of the daemon.
+pub struct Client {
+    reader: BufReader<UnixStream>,
+    writer: UnixStream,
+}
+
+impl Client {
+    pub fn connect(socket: &Path) -> Result<Client> {
+        let writer = UnixStream::connect(socket)?;
+        Ok(Client {
+            reader: BufReader::new(writer.try_clone()?),
+            writer,
+        })
+    }
+
+    /// Connects to the socket the daemon listens on by default.
+    pub fn connect_default() -> Result<Client> {
+        Client::connect(&default_config()?.socket)
+    }
+
+    fn send(&mut self, command: Command) -> Result<()> {
+        let request = Request {
+            version: PROTOCOL_VERSION,
+            command,
+        };
+        let mut line =
+            serde_json::to_vec(&request).map_err(|e| ClientError::Protocol(e.to_string()))?;
+        line.push(b'\n');
+        Ok(self.writer.write_all(&line)?)
+    }
+
+    /// The next response, or `None` once the daemon hung up.
+    fn receive(&mut self) -> Result<Option<Body>> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let response: Response =
+            serde_json::from_str(&line).map_err(|e| ClientError::Protocol(e.to_string()))?;
+        if response.version != PROTOCOL_VERSION {
+            return Err(ClientError::Protocol(format!(
+                "version {}, expected {}",
+                response.version, PROTOCOL_VERSION
+            )));
+        }
+        Ok(Some(response.body))
+    }
+
+    fn call<T: DeserializeOwned>(&mut self, command: Command) -> Result<T> {
+        self.send(command)?;
+        match self.receive()? {
+            Some(Body::Ok { result }) => {
+                serde_json::from_value(result).map_err(|e| ClientError::Protocol(e.to_string()))
+            }
+            Some(Body::Error { error }) => Err(ClientError::Daemon(error)),
+            Some(Body::Event { .. }) => Err(ClientError::Protocol("unexpected event".to_string())),
+            None => Err(ClientError::Protocol("connection closed".to_string())),
+        }
+    }
+
+    /// The first `LIST_LIMIT` pending expirations, earliest first,
+    /// optionally only those due before `due_before` or under `root`.
+    pub fn list(&mut self, due_before: Option<i64>, root: Option<&Path>) -> Result<Vec<Entry>> {
+        let root = root.map(absolute).transpose()?;
+        self.call(Command::List {
+            due_before,
+            root,
+            limit: None,
+        })
+    }
+
+    /// Every expiration of `path`, most recent first, with their history.
+    pub fn show(&mut self, path: &Path) -> Result<Vec<Shown>> {
+        let path = absolute(path)?;
+        self.call(Command::Show { path })
+    }
+
+    /// Cancels the pending expirations of `path` and removes its
+    /// `user.expire_at`. Returns how many were cancelled.
+    pub fn cancel(&mut self, path: &Path) -> Result<usize> {
+        let path = absolute(path)?;
+        let Cancelled { cancelled } = self.call(Command::Cancel { path })?;
+        Ok(cancelled)
+    }
+
+    /// Pushes the expiration of `path` back by `by`, returning the new one.
+    pub fn extend(&mut self, path: &Path, by: Duration) -> Result<i64> {
+        let path = absolute(path)?;
+        let Extended { expire_at } = self.call(Command::Extend {
+            path,
+            by: by.as_secs(),
+        })?;
+        Ok(expire_at)
+    }
+
+    /// Makes the daemon scan its rules and expire what's due right away.
+    pub fn sweep(&mut self) -> Result<()> {
+        self.call(Command::Sweep)
+    }
+
+    /// Makes the daemon load its config and rules again.
+    pub fn reload(&mut self) -> Result<Reloaded> {
+        self.call(Command::Reload)
+    }
+
+    /// Waits until the daemon registered that `path` expires at
+    /// `expire_at`, as returned by [`expire_at`], for up to `timeout`.
+    pub fn confirm(&mut self, path: &Path, expire_at: i64, timeout: Duration) -> Result<Entry> {
+        self.confirm_with_name(path, &watched_xattr()?, expire_at, timeout)
+    }
+
+    /// Like [`Client::confirm`], for a daemon watching the `name` xattr.
+    pub fn confirm_with_name(
+        &mut self,
+        path: &Path,
+        name: &str,
+        expire_at: i64,
+        timeout: Duration,
+    ) -> Result<Entry> {
+        let started = Instant::now();
+        loop {
+            // Not one already expired or cancelled for the same deadline.
+            let registered = self.show(path)?.into_iter().find(|shown| {
+                shown.entry.state == State::Pending
+                    && shown.entry.name == name
+                    && shown.entry.expire_at == expire_at
+            });
+            if let Some(shown) = registered {
+                return Ok(shown.entry);
+            }
+            if started.elapsed() >= timeout {
+                return Err(ClientError::Timeout);
+            }
+            thread::sleep(CONFIRM_INTERVAL);
+        }
+    }
+
+    /// Streams what happens to the expirations, for root and the user the
+    /// daemon runs as.
+    pub fn subscribe(mut self) -> Result<Subscription> {
+        self.call::<()>(Command::Subscribe)?;
+        Ok(Subscription { client: self })
+    }
+}
+
+/// Notifications from the daemon, until it goes away.
+pub struct Subscription {
+    client: Client,
+}
+
+impl Iterator for Subscription {
+    type Item = Result<Notification>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.client.receive() {
+            Ok(Some(Body::Event { event })) => Some(Ok(*event)),
+            Ok(Some(Body::Error { error })) => Some(Err(ClientError::Daemon(error))),
+            Ok(Some(Body::Ok { .. })) => Some(Err(ClientError::Protocol(
+                "unexpected response".to_string(),
+            ))),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::os::unix::net::UnixListener;
+    use tempfile::TempDir;
+
+    /// A daemon answering each request with the next of `answers`, the last
+    /// one over and over.
+    fn daemon(dir: &Path, answers: Vec<Response>) -> Client {
+        let socket = dir.join("daemon.sock");
+        let listener = UnixListener::bind(&socket).unwrap();
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut writer = stream;
+            let mut line = String::new();
+            let mut answered = 0;
+            while reader.read_line(&mut line).unwrap_or(0) > 0 {
+                let answer = &answers[answered.min(answers.len() - 1)];
+                let mut reply = serde_json::to_vec(answer).unwrap();
+                reply.push(b'\n');
+                if writer.write_all(&reply).is_err() {
+                    break;
+                }
+                answered += 1;
+                line.clear();
+            }
+        });
+        Client::connect(&socket).unwrap()
+    }
+
+    fn shown(path: &Path, id: i64, state: State, expire_at: i64) -> Shown {
+        Shown {
+            entry: Entry {
+                id: Some(id),
+                path: path.to_path_buf(),
+                name: xattr::EXPIRE_AT.to_string(),
+                expire_at,
+                state,
+                ..Entry::default()
+            },
+            history: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn rounds_deadlines_up() {
+        let at = UNIX_EPOCH + Duration::from_millis(1500);
+        assert_eq!(timestamp(at).unwrap(), 2);
+        assert_eq!(timestamp(UNIX_EPOCH + Duration::from_secs(2)).unwrap(), 2);
+        assert!(matches!(
+            timestamp(UNIX_EPOCH - Duration::from_secs(1)),
+            Err(ClientError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn refuses_paths_the_daemon_cannot_read_in_full() {
+        let long = PathBuf::from("/").join("a".repeat(MAX_PATH_LEN));
+        assert!(matches!(absolute(&long), Err(ClientError::Invalid(_))));
+        assert!(absolute(Path::new("relative")).unwrap().is_absolute());
+    }
+
+    #[test]
+    fn sets_reads_and_clears_expirations() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("file");
+        fs::write(&path, b"").unwrap();
+        let name = "user.test_expire_at";
+        let at = UNIX_EPOCH + Duration::from_secs(4102444800);
+
+        assert_eq!(expire_at_with_name(&path, name, at).unwrap(), 4102444800);
+        assert_eq!(expiration_with_name(&path, name).unwrap(), Some(at));
+        assert!(clear_with_name(&path, name).unwrap());
+        assert!(!clear_with_name(&path, name).unwrap());
+        assert_eq!(expiration_with_name(&path, name).unwrap(), None);
+
+        let expire_at = expire_in_with_name(&path, name, Duration::from_secs(60)).unwrap();
+        assert!(expire_at > timestamp(SystemTime::now()).unwrap());
+        xattr::set(&path, name, b"soon").unwrap();
+        assert!(matches!(
+            expiration_with_name(&path, name),
+            Err(ClientError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn confirms_only_pending_expirations() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("file");
+        let mut client = daemon(
+            tmp.path(),
+            vec![
+                Response::ok(&[shown(&path, 1, State::Deleted, 100)]),
+                Response::ok(&[
+                    shown(&path, 2, State::Pending, 100),
+                    shown(&path, 1, State::Deleted, 100),
+                ]),
+            ],
+        );
+
+        let entry = client
+            .confirm_with_name(&path, xattr::EXPIRE_AT, 100, Duration::from_secs(5))
+            .unwrap();
+
+        assert_eq!(entry.id, Some(2));
+    }
+
+    #[test]
+    fn gives_up_confirming_after_the_timeout() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("file");
+        let mut client = daemon(
+            tmp.path(),
+            vec![Response::ok(&[
+                shown(&path, 1, State::Cancelled, 100),
+                shown(&path, 2, State::Pending, 200),
+            ])],
+        );
+
+        let confirmed =
+            client.confirm_with_name(&path, xattr::EXPIRE_AT, 100, Duration::from_millis(200));
+
+        assert!(matches!(confirmed, Err(ClientError::Timeout)));
+    }
+
+    #[test]
+    fn reports_what_the_daemon_refuses() {
+        let tmp = TempDir::new().unwrap();
+        let mut client = daemon(
+            tmp.path(),
+            vec![Response {
+                version: PROTOCOL_VERSION,
+                body: Body::Error {
+                    error: "no".to_string(),
+                },
+            }],
+        );
+
+        assert!(matches!(client.sweep(), Err(ClientError::Daemon(e)) if e == "no"));
+    }
+}
@@ -0,0 +1,392 @@
+use crate::action::{self, ACTIONS};
+use crate::event::MAX_NAME_LEN;
+use crate::log::{Format, Level};
+use crate::retry::MAX_ATTEMPTS;
+use crate::xattr;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const CONFIG_PATH: &str = "/etc/sweeper/sweeper.toml";
+pub const DEFAULT_DB_PATH: &str = "/var/lib/sweeper/sweeper.db";
+pub const DEFAULT_RUNTIME_DIR: &str = "/run/sweeper";
+const LOCK_FILE: &str = "sweeper.lock";
+const SOCKET_FILE: &str = "sweeper.sock";
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Where the daemon keeps its state.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    /// The SQLite database at `db_path`.
+    #[default]
+    Sqlite,
+    /// Nothing survives a restart, for tests and ephemeral hosts.
+    Memory,
+}
+
+/// The extended attributes the daemon looks at.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Xattrs {
+    /// Holds the timestamp at which a file expires.
+    pub expire_at: String,
+    /// Selects what happens to a file once it expires.
+    pub action: String,
+}
+
+impl Default for Xattrs {
+    fn default() -> Self {
+        Xattrs {
+            expire_at: xattr::EXPIRE_AT.to_string(),
+            action: xattr::EXPIRE_ACTION.to_string(),
+        }
+    }
+}
+
+/// How much work the daemon does at once, and how often.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Limits {
+    /// Events waiting to be written to the store. Once full, the BPF
+    /// callback waits, the perf buffer fills up and the kernel drops
+    /// events, which are counted as lost. Only read on startup.
+    pub channel_capacity: usize,
+    /// Events written to the store in a single transaction.
+    pub ingest_batch: usize,
+    /// Due entries handled per iteration of the cleaner, so a backlog
+    /// doesn't have to be loaded in memory all at once.
+    pub sweep_batch: usize,
+    /// Attempts made at expiring a file before giving up on it.
+    pub max_attempts: u32,
+    /// Seconds between two scans of the directories covered by rules.
+    pub rules_interval: u64,
+    /// Seconds between two reports of the ingestion metrics.
+    pub metrics_interval: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            channel_capacity: 10_000,
+            ingest_batch: 500,
+            sweep_batch: 1000,
+            max_attempts: MAX_ATTEMPTS,
+            rules_interval: 60,
+            metrics_interval: 60,
+        }
+    }
+}
+
+/// What the daemon prints, and how.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Log {
+    /// Every xattr change seen by the BPF program, which can be a lot.
+    pub events: bool,
+    pub level: Level,
+    pub format: Format,
+}
+
+impl Default for Log {
+    fn default() -> Self {
+        Log {
+            events: true,
+            level: Level::Info,
+            format: Format::Auto,
+        }
+    }
+}
+
+/// Where an instance keeps its state and how it behaves, as found in the
+/// config file or on the command line. Unset values fall back to the
+/// defaults.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Settings {
+    pub db_path: Option<PathBuf>,
+    pub lock_file: Option<PathBuf>,
+    pub socket: Option<PathBuf>,
+    pub runtime_dir: Option<PathBuf>,
+    pub store: Option<Backend>,
+    /// Seconds given to the daemon to drain its queues and finish the
+    /// current expiration when asked to stop.
+    pub shutdown_timeout: Option<u64>,
+    /// Only files under these directories are expired through their xattr.
+    pub roots: Option<Vec<PathBuf>>,
+    /// The kinds of actions that may be carried out.
+    pub actions: Option<Vec<String>>,
+    #[serde(default)]
+    pub xattr: Xattrs,
+    #[serde(default)]
+    pub limits: Limits,
+    #[serde(default)]
+    pub log: Log,
+}
+
+impl Settings {
+    /// Takes the values set in `overrides` over ours.
+    pub fn merge(self, overrides: Settings) -> Settings {
+        Settings {
+            db_path: overrides.db_path.or(self.db_path),
+            lock_file: overrides.lock_file.or(self.lock_file),
+            socket: overrides.socket.or(self.socket),
+            runtime_dir: overrides.runtime_dir.or(self.runtime_dir),
+            store: overrides.store.or(self.store),
+            shutdown_timeout: overrides.shutdown_timeout.or(self.shutdown_timeout),
+            // Only found in the config file.
+            roots: self.roots,
+            actions: self.actions,
+            xattr: self.xattr,
+            limits: self.limits,
+            log: self.log,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub db_path: PathBuf,
+    /// Held exclusively by the running daemon.
+    pub lock_file: PathBuf,
+    /// The control socket of the daemon.
+    pub socket: PathBuf,
+    pub runtime_dir: PathBuf,
+    pub store: Backend,
+    pub shutdown_timeout: Duration,
+    /// Empty to watch the whole filesystem.
+    pub roots: Vec<PathBuf>,
+    /// Every kind of action if unset.
+    pub actions: Option<Vec<String>>,
+    pub xattr: Xattrs,
+    pub limits: Limits,
+    pub log: Log,
+}
+
+impl From<Settings> for Config {
+    fn from(settings: Settings) -> Self {
+        let runtime_dir = settings
+            .runtime_dir
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_RUNTIME_DIR));
+        Config {
+            db_path: settings
+                .db_path
+                .unwrap_or_else(|| PathBuf::from(DEFAULT_DB_PATH)),
+            lock_file: settings
+                .lock_file
+                .unwrap_or_else(|| runtime_dir.join(LOCK_FILE)),
+            socket: settings
+                .socket
+                .unwrap_or_else(|| runtime_dir.join(SOCKET_FILE)),
+            runtime_dir,
+            store: settings.store.unwrap_or_default(),
+            shutdown_timeout: settings
+                .shutdown_timeout
+                .map_or(DEFAULT_SHUTDOWN_TIMEOUT, Duration::from_secs),
+            roots: settings.roots.unwrap_or_default(),
+            actions: settings.actions,
+            xattr: settings.xattr,
+            limits: settings.limits,
+            log: settings.log,
+        }
+    }
+}
+
+impl Config {
+    /// Catches the mistakes that would otherwise only show up once files
+    /// start expiring, or not.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(root) = self.roots.iter().find(|root| !root.is_absolute()) {
+            return Err(format!("roots: {} must be absolute", root.display()));
+        }
+        for action in self.actions.iter().flatten() {
+            if !ACTIONS.contains(&action.as_str()) {
+                return Err(format!(
+                    "actions: unknown action `{}`, expected one of {}",
+                    action,
+                    ACTIONS.join(", ")
+                ));
+            }
+        }
+        for name in [&self.xattr.expire_at, &self.xattr.action] {
+            // The BPF program only reads that much of it.
+            if name.is_empty() || name.len() > MAX_NAME_LEN || !name.contains('.') {
+                return Err(format!(
+                    "xattr: `{}` must be a namespaced name of at most {} bytes, e.g. `user.expire_at`",
+                    name, MAX_NAME_LEN
+                ));
+            }
+        }
+        if self.xattr.expire_at == self.xattr.action {
+            return Err("xattr: expire_at and action must differ".to_string());
+        }
+        let limits = &self.limits;
+        if [
+            limits.channel_capacity,
+            limits.ingest_batch,
+            limits.sweep_batch,
+        ]
+        .contains(&0)
+            || limits.max_attempts == 0
+            || limits.rules_interval == 0
+            || limits.metrics_interval == 0
+        {
+            return Err("limits: must all be greater than 0".to_string());
+        }
+        Ok(())
+    }
+
+    /// Whether files may be expired with the action `spec`.
+    pub fn allows(&self, spec: &str) -> bool {
+        let kind = action::kind(spec);
+        self.actions
+            .as_ref()
+            .is_none_or(|actions| actions.iter().any(|action| action == kind))
+    }
+
+    /// Whether `path` is under one of the watched roots.
+    pub fn watches(&self, path: &Path) -> bool {
+        self.roots.is_empty() || self.roots.iter().any(|root| path.starts_with(root))
+    }
+
+    /// The settings that differ from `other` but only apply on startup.
+    pub fn needs_restart(&self, other: &Config) -> Vec<&'static str> {
+        [
+            ("db_path", self.db_path != other.db_path),
+            ("lock_file", self.lock_file != other.lock_file),
+            ("socket", self.socket != other.socket),
+            ("runtime_dir", self.runtime_dir != other.runtime_dir),
+            ("store", self.store != other.store),
+            (
+                "limits.channel_capacity",
+                self.limits.channel_capacity != other.limits.channel_capacity,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(name, changed)| changed.then_some(name))
+        .collect()
+    }
+}
+
+/// Where the config comes from, to load it again on reload.
+#[derive(Debug, Clone)]
+pub struct Source {
+    pub path: PathBuf,
+    /// Whether the file was asked for explicitly, so it must exist.
+    pub required: bool,
+    /// From the command line, taken over the file.
+    pub overrides: Settings,
+}
+
+impl Source {
+    pub fn load(&self) -> Result<Config, String> {
+        let settings = load_config(&self.path, self.required)?;
+        let config = Config::from(settings.merge(self.overrides.clone()));
+        config
+            .validate()
+            .map_err(|e| format!("{}: {e}", self.path.display()))?;
+        Ok(config)
+    }
+}
+
+/// Reads the config file at `path`. A missing file is the same as an
+/// empty one, unless `required` is set.
+pub fn load_config(path: &Path, required: bool) -> Result<Settings, String> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && !required => {
+            return Ok(Settings::default())
+        }
+        Err(e) => return Err(format!("{}: {e}", path.display())),
+    };
+    toml::from_str(&contents).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn config(toml: &str) -> Result<Config, String> {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(toml.as_bytes()).unwrap();
+        Source {
+            path: file.path().to_path_buf(),
+            required: true,
+            overrides: Settings::default(),
+        }
+        .load()
+    }
+
+    #[test]
+    fn loads_the_defaults_from_an_empty_file() {
+        let config = config("").unwrap();
+        assert_eq!(config.xattr, Xattrs::default());
+        assert_eq!(config.limits, Limits::default());
+    }
+
+    #[test]
+    fn refuses_unknown_keys() {
+        assert!(config("colour = true").is_err());
+        assert!(config("[xattr]\nexpire = \"user.ttl\"").is_err());
+        assert!(config("[limits]\nbatch = 1").is_err());
+        assert!(config("[log]\nfile = \"/var/log/sweeper\"").is_err());
+    }
+
+    #[test]
+    fn refuses_relative_roots() {
+        assert!(config("roots = [\"tmp\"]").is_err());
+        assert!(config("roots = [\"/tmp\"]").is_ok());
+    }
+
+    #[test]
+    fn refuses_zero_limits() {
+        for limit in [
+            "channel_capacity",
+            "ingest_batch",
+            "sweep_batch",
+            "max_attempts",
+            "rules_interval",
+            "metrics_interval",
+        ] {
+            assert!(
+                config(&format!("[limits]\n{limit} = 0")).is_err(),
+                "{limit}"
+            );
+        }
+    }
+
+    #[test]
+    fn refuses_unknown_actions_and_bad_xattr_names() {
+        assert!(config("actions = [\"burn\"]").is_err());
+        assert!(config("actions = [\"delete\", \"trash\"]").is_ok());
+        assert!(config("[xattr]\nexpire_at = \"expire_at\"").is_err());
+        assert!(config("[xattr]\naction = \"user.expire_at\"").is_err());
+        let longest = format!("user.{}", "a".repeat(MAX_NAME_LEN - 5));
+        assert!(config(&format!("[xattr]\nexpire_at = \"{longest}\"")).is_ok());
+        assert!(config(&format!("[xattr]\nexpire_at = \"{longest}a\"")).is_err());
+    }
+
+    #[test]
+    fn lists_what_only_a_restart_changes() {
+        let before = config("").unwrap();
+        let after = config(
+            "socket = \"/tmp/other.sock\"
+            store = \"memory\"
+            [limits]
+            channel_capacity = 10
+            sweep_batch = 10
+            [log]
+            level = \"debug\"",
+        )
+        .unwrap();
+
+        assert_eq!(
+            after.needs_restart(&before),
+            ["socket", "store", "limits.channel_capacity"]
+        );
+        assert!(before.needs_restart(&before).is_empty());
+    }
+}
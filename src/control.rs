@@ -0,0 +1,255 @@
+use crate::escape;
+use crate::state::State;
+use crate::store::{Entry, Transition};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::mem;
+use std::os::unix::io::AsRawFd;
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::sync::Mutex;
+
+/// Version of the protocol spoken on the control socket, bumped whenever a
+/// change would break existing clients.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// How many pending expirations `list` returns when not told otherwise.
+pub const LIST_LIMIT: usize = 1000;
+
+// Notifications a subscriber can lag behind before being dropped.
+const SUBSCRIBER_CAPACITY: usize = 1024;
+
+/// What a client sends, as a single line of JSON.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Request {
+    pub version: u32,
+    #[serde(flatten)]
+    pub command: Command,
+}
+
+/// Paths are escaped like in the output of the CLI, so the ones that
+/// aren't UTF-8 can be sent too.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum Command {
+    /// Pending expirations, earliest first, optionally only those due
+    /// before a timestamp or under a directory. At most `limit`, or
+    /// `LIST_LIMIT`.
+    List {
+        #[serde(default)]
+        due_before: Option<i64>,
+        #[serde(default, with = "escape::option")]
+        root: Option<PathBuf>,
+        #[serde(default)]
+        limit: Option<usize>,
+    },
+    /// Every expiration of a file, with their history.
+    Show {
+        #[serde(
+            serialize_with = "escape::serialize",
+            deserialize_with = "escape::deserialize"
+        )]
+        path: PathBuf,
+    },
+    /// Cancels the pending expirations of a file and removes its
+    /// `user.expire_at` xattr.
+    Cancel {
+        #[serde(
+            serialize_with = "escape::serialize",
+            deserialize_with = "escape::deserialize"
+        )]
+        path: PathBuf,
+    },
+    /// Pushes the `user.expire_at` of a file back by `by` seconds.
+    Extend {
+        #[serde(
+            serialize_with = "escape::serialize",
+            deserialize_with = "escape::deserialize"
+        )]
+        path: PathBuf,
+        by: u64,
+    },
+    /// Scans the rules and expires what's due right away.
+    Sweep,
+    /// Loads the config and the rules again.
+    Reload,
+    /// Turns the connection into a stream of notifications.
+    Subscribe,
+}
+
+/// What the daemon answers, as a single line of JSON. After a `subscribe`,
+/// one `event` follows for every notification.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Response {
+    pub version: u32,
+    #[serde(flatten)]
+    pub body: Body,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Body {
+    Ok { result: serde_json::Value },
+    Error { error: String },
+    Event { event: Box<Notification> },
+}
+
+impl Response {
+    pub fn ok<T: Serialize>(result: &T) -> Response {
+        match serde_json::to_value(result) {
+            Ok(result) => Response {
+                version: PROTOCOL_VERSION,
+                body: Body::Ok { result },
+            },
+            Err(e) => Response::error(e.to_string()),
+        }
+    }
+
+    pub fn error(error: impl Into<String>) -> Response {
+        Response {
+            version: PROTOCOL_VERSION,
+            body: Body::Error {
+                error: error.into(),
+            },
+        }
+    }
+
+    pub fn event(event: Notification) -> Response {
+        Response {
+            version: PROTOCOL_VERSION,
+            body: Body::Event {
+                event: Box::new(event),
+            },
+        }
+    }
+}
+
+/// An entry along with its audit trail, as returned by `show`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Shown {
+    #[serde(flatten)]
+    pub entry: Entry,
+    pub history: Vec<Transition>,
+}
+
+/// Result of `cancel`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Cancelled {
+    pub cancelled: usize,
+}
+
+/// Result of `extend`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Extended {
+    pub expire_at: i64,
+}
+
+/// Result of `reload`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reloaded {
+    pub rules: usize,
+    /// Settings that changed but only apply on restart.
+    #[serde(default)]
+    pub restart_needed: Vec<String>,
+}
+
+/// Something that happened to the expirations, streamed to subscribers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Notification {
+    /// A file was scheduled to expire through its xattr.
+    Scheduled { entry: Entry },
+    /// The deadline of an entry moved.
+    Rescheduled { entry_id: i64, expire_at: i64 },
+    /// An entry went from a state to another.
+    Changed {
+        entry_id: i64,
+        #[serde(
+            serialize_with = "escape::serialize",
+            deserialize_with = "escape::deserialize"
+        )]
+        path: PathBuf,
+        from: State,
+        to: State,
+        error: Option<String>,
+    },
+    /// The pending expirations of a file were cancelled.
+    Cancelled {
+        #[serde(
+            serialize_with = "escape::serialize",
+            deserialize_with = "escape::deserialize"
+        )]
+        path: PathBuf,
+        name: String,
+        count: usize,
+        reason: String,
+    },
+}
+
+/// Hands the notifications over to every subscriber. One that falls too
+/// far behind is dropped, rather than slowing the daemon down.
+#[derive(Debug, Default)]
+pub struct Hub {
+    subscribers: Mutex<Vec<SyncSender<Notification>>>,
+}
+
+impl Hub {
+    pub fn subscribe(&self) -> Receiver<Notification> {
+        let (sender, receiver) = sync_channel(SUBSCRIBER_CAPACITY);
+        self.lock().push(sender);
+        receiver
+    }
+
+    /// Whether anyone is listening, to skip building notifications
+    /// otherwise.
+    pub fn has_subscribers(&self) -> bool {
+        !self.lock().is_empty()
+    }
+
+    pub fn publish(&self, notification: Notification) {
+        self.lock()
+            .retain(|subscriber| subscriber.try_send(notification.clone()).is_ok());
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<SyncSender<Notification>>> {
+        self.subscribers.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// The process on the other end of the socket, as vouched for by the
+/// kernel.
+#[derive(Debug, Clone, Copy)]
+pub struct Peer {
+    pub pid: i32,
+    pub uid: u32,
+    pub gid: u32,
+}
+
+/// Reads the credentials of the peer with `SO_PEERCRED`. They are the ones
+/// it had when connecting, and can't be forged.
+pub fn peer(stream: &UnixStream) -> io::Result<Peer> {
+    let mut cred = libc::ucred {
+        pid: 0,
+        uid: 0,
+        gid: 0,
+    };
+    let mut len = mem::size_of::<libc::ucred>() as libc::socklen_t;
+    let ret = unsafe {
+        libc::getsockopt(
+            stream.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_PEERCRED,
+            (&mut cred as *mut libc::ucred).cast(),
+            &mut len,
+        )
+    };
+    if ret != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(Peer {
+        pid: cred.pid,
+        uid: cred.uid,
+        gid: cred.gid,
+    })
+}
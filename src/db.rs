@@ -0,0 +1,52 @@
+use crate::migrations;
+use rusqlite::Connection;
+use std::fs::DirBuilder;
+use std::os::unix::fs::DirBuilderExt;
+use std::path::Path;
+use std::time::Duration;
+
+// How long a connection waits for another one holding the write lock.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+// Prepared statements kept around per connection, enough for every query
+// the daemon runs.
+const STATEMENT_CACHE_CAPACITY: usize = 64;
+
+/// Opens the database at `path`, tuned for the daemon, and migrates its
+/// schema if needed. Its directory is created if missing.
+///
+/// The database is switched to WAL mode, so the cleaner reading due
+/// entries doesn't block the ingestion of new ones and the other way
+/// around. `synchronous = NORMAL` is enough with WAL: a power loss can only
+/// lose the last transactions, never corrupt the database, and entries lost
+/// that way are at worst left in `deleting`, which is recovered on startup.
+pub fn open(path: &Path) -> Result<Connection, String> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(parent)
+            .map_err(|e| format!("{}: {e}", parent.display()))?;
+    }
+    let conn = Connection::open(path).map_err(|e| e.to_string())?;
+    conn.busy_timeout(BUSY_TIMEOUT).map_err(|e| e.to_string())?;
+    conn.set_prepared_statement_cache_capacity(STATEMENT_CACHE_CAPACITY);
+
+    let journal_mode: String = conn
+        .pragma_update_and_check(None, "journal_mode", "wal", |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+    if journal_mode != "wal" && journal_mode != "memory" {
+        crate::warn!(
+            path = path;
+            "Warning: {}: WAL mode unavailable, using `{journal_mode}`",
+            path.display()
+        );
+    }
+    conn.pragma_update(None, "synchronous", "NORMAL")
+        .map_err(|e| e.to_string())?;
+
+    migrations::migrate(&conn)?;
+    Ok(conn)
+}
@@ -0,0 +1,68 @@
+use crate::event::DecodeError;
+use crate::store::StoreError;
+use std::fmt;
+
+/// Everything that can go wrong in the daemon.
+#[derive(Debug)]
+pub enum SweeperError {
+    /// Opening, loading, attaching or polling the BPF program.
+    Bpf {
+        context: &'static str,
+        source: libbpf_rs::Error,
+    },
+    /// Reading or writing the store.
+    Store(StoreError),
+    /// An event from the BPF program that doesn't make sense.
+    Parse(String),
+    /// Picking or running the action for an expired file.
+    Action(String),
+    /// Loading the rules.
+    Rules(String),
+    /// A worker thread panicked.
+    Thread(String),
+    /// Setting up or serving the control socket.
+    Control(String),
+}
+
+impl fmt::Display for SweeperError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SweeperError::Bpf { context, source } => write!(f, "{}: {}", context, source),
+            SweeperError::Store(e) => write!(f, "store: {}", e),
+            SweeperError::Parse(e) => write!(f, "invalid event: {}", e),
+            SweeperError::Action(e) => f.write_str(e),
+            SweeperError::Rules(e) => write!(f, "loading rules: {}", e),
+            SweeperError::Thread(e) => write!(f, "thread panicked: {}", e),
+            SweeperError::Control(e) => write!(f, "control socket: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SweeperError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SweeperError::Bpf { source, .. } => Some(source),
+            SweeperError::Store(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<StoreError> for SweeperError {
+    fn from(e: StoreError) -> Self {
+        SweeperError::Store(e)
+    }
+}
+
+impl From<DecodeError> for SweeperError {
+    fn from(e: DecodeError) -> Self {
+        SweeperError::Parse(e.to_string())
+    }
+}
+
+impl SweeperError {
+    /// Wraps a libbpf error with what we were doing.
+    pub fn bpf(context: &'static str) -> impl FnOnce(libbpf_rs::Error) -> Self {
+        move |source| SweeperError::Bpf { context, source }
+    }
+}
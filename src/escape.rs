@@ -0,0 +1,161 @@
+use serde::{Deserialize, Deserializer, Serializer};
+use std::ffi::{OsStr, OsString};
+use std::fmt::{self, Write};
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+
+/// Shows a path, or any other bytes from the kernel, without losing any of
+/// them: valid UTF-8 is printed as is, except for backslashes which are
+/// doubled, and every other byte as `\xNN`, control characters included so
+/// a name can't break a line of output.
+pub struct Escaped<'a>(&'a [u8]);
+
+pub fn escape<S: AsRef<OsStr> + ?Sized>(s: &S) -> Escaped<'_> {
+    Escaped(s.as_ref().as_bytes())
+}
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for chunk in self.0.utf8_chunks() {
+            for c in chunk.valid().chars() {
+                match c {
+                    '\\' => f.write_str("\\\\")?,
+                    c if c.is_ascii_control() => write!(f, "\\x{:02x}", c as u8)?,
+                    c => f.write_char(c)?,
+                }
+            }
+            for byte in chunk.invalid() {
+                write!(f, "\\x{:02x}", byte)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Serializes a path escaped, for `#[serde(serialize_with)]`. Serde would
+/// refuse the ones that aren't UTF-8 otherwise.
+pub fn serialize<S: Serializer, P: AsRef<OsStr>>(
+    path: &P,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&escape(path))
+}
+
+/// Reverses [`escape`], for bytes read back from the output or sent by a
+/// client.
+pub fn unescape(s: &str) -> Result<OsString, String> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut rest = s.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        rest = tail;
+        if byte != b'\\' {
+            bytes.push(byte);
+            continue;
+        }
+        match rest {
+            [b'\\', tail @ ..] => {
+                bytes.push(b'\\');
+                rest = tail;
+            }
+            [b'x', hi, lo, tail @ ..] => {
+                // `from_str_radix` alone would take a sign, as in `\x+1`.
+                let hex = std::str::from_utf8(&[*hi, *lo])
+                    .ok()
+                    .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or_else(|| format!("invalid escape in `{}`", s))?;
+                bytes.push(hex);
+                rest = tail;
+            }
+            _ => return Err(format!("invalid escape in `{}`", s)),
+        }
+    }
+    Ok(OsString::from_vec(bytes))
+}
+
+/// Deserializes a path escaped by [`serialize`], for
+/// `#[serde(deserialize_with)]`.
+pub fn deserialize<'de, D: Deserializer<'de>, P: From<OsString>>(
+    deserializer: D,
+) -> Result<P, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    unescape(&s).map(P::from).map_err(serde::de::Error::custom)
+}
+
+/// [`serialize`] and [`deserialize`] for optional paths, for
+/// `#[serde(with = "escape::option")]`.
+pub mod option {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::ffi::OsStr;
+    use std::path::PathBuf;
+
+    pub fn serialize<S: Serializer, P: AsRef<OsStr>>(
+        path: &Option<P>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match path {
+            Some(path) => super::serialize(path, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<PathBuf>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|s| super::unescape(&s).map(PathBuf::from))
+            .transpose()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_any_bytes() {
+        for bytes in [
+            &b"/tmp/plain"[..],
+            "/tmp/caf\u{e9} \u{1f9f9}".as_bytes(),
+            b"/tmp/caf\xe9",
+            b"/tmp/back\\slash\\",
+            b"/tmp/\\x41",
+            b"/tmp/new\nline\t\x7f\0",
+            b"\xff\xfe\x80",
+        ] {
+            let escaped = escape(OsStr::from_bytes(bytes)).to_string();
+            assert_eq!(unescape(&escaped).unwrap().as_bytes(), bytes, "{escaped}");
+        }
+    }
+
+    #[test]
+    fn escapes_what_could_be_mistaken() {
+        let escape = |bytes: &[u8]| escape(OsStr::from_bytes(bytes)).to_string();
+        assert_eq!(escape("caf\u{e9}".as_bytes()), "caf\u{e9}");
+        assert_eq!(escape(b"caf\xe9"), "caf\\xe9");
+        assert_eq!(escape(b"a\\b"), "a\\\\b");
+        assert_eq!(escape(b"\\x41"), "\\\\x41");
+        assert_eq!(escape(b"a\nb"), "a\\x0ab");
+    }
+
+    #[test]
+    fn unescapes_hex_in_any_case() {
+        assert_eq!(unescape("\\xE9\\x41").unwrap().as_bytes(), b"\xe9A");
+    }
+
+    #[test]
+    fn refuses_malformed_escapes() {
+        for s in [
+            "\\",
+            "a\\",
+            "\\q",
+            "\\x",
+            "\\x4",
+            "\\xzz",
+            "\\x+1",
+            "\\x\u{e9}9",
+        ] {
+            assert!(unescape(s).is_err(), "{s}");
+        }
+    }
+}
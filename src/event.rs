@@ -0,0 +1,286 @@
+use crate::sweeper::sweeper_bss_types::event_t;
+use std::ffi::{CStr, OsStr, OsString};
+use std::fmt;
+use std::os::unix::ffi::OsStrExt;
+use std::path::PathBuf;
+use std::{mem, ptr};
+
+/// Version of the records sent by the BPF program, bumped whenever
+/// `struct event_t` in `sweeper.bpf.c` changes.
+pub const EVENT_VERSION: u16 = 1;
+
+/// Size of the record, with the padding the compiler adds at the end.
+pub const EVENT_SIZE: usize = mem::size_of::<event_t>();
+
+/// Longest path known to be read in full by the BPF program. It truncates
+/// longer ones to fill the field, so a path filling it might have been cut
+/// short, and is refused rather than recorded wrong.
+pub const MAX_PATH_LEN: usize = mem::offset_of!(event_t, name) - mem::offset_of!(event_t, path) - 2;
+
+/// Longest xattr name known to be read in full by the BPF program, which
+/// truncates longer ones the same way as paths.
+pub const MAX_NAME_LEN: usize =
+    mem::offset_of!(event_t, value) - mem::offset_of!(event_t, name) - 2;
+
+// `event_t` is generated from the BTF of the BPF program, so it always
+// matches it. Its layout is pinned here all the same: changing it needs a
+// new `EVENT_VERSION`, and the header has to stay where it's read from
+// before the rest of the record is trusted.
+const _: () = {
+    assert!(mem::offset_of!(event_t, version) == 0);
+    assert!(mem::offset_of!(event_t, size) == 2);
+    assert!(mem::offset_of!(event_t, pid) == 4);
+    assert!(mem::offset_of!(event_t, uid) == 8);
+    assert!(mem::offset_of!(event_t, comm) == 12);
+    assert!(mem::offset_of!(event_t, path) == 28);
+    assert!(mem::offset_of!(event_t, name) == 78);
+    assert!(mem::offset_of!(event_t, value) == 128);
+    assert!(EVENT_SIZE == 180);
+};
+
+/// A successful `[l]setxattr(2)` call, as seen by the BPF program. Paths
+/// and xattrs are bytes, kept as they are even if they aren't UTF-8.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub pid: u32,
+    pub uid: u32,
+    pub comm: String,
+    pub path: PathBuf,
+    pub name: OsString,
+    pub value: OsString,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// Not even the header fits.
+    Truncated { len: usize },
+    /// Sent by a different version of the BPF program.
+    Version(u16),
+    /// The header disagrees with the record layout or the buffer.
+    Size { size: u16, len: usize },
+    /// A string field without its terminating NUL.
+    Unterminated(&'static str),
+    /// A path filling its field, likely truncated by the BPF program.
+    PathTooLong,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::Truncated { len } => write!(f, "truncated record of {} byte(s)", len),
+            DecodeError::Version(version) => {
+                write!(f, "record version {}, expected {}", version, EVENT_VERSION)
+            }
+            DecodeError::Size { size, len } => write!(
+                f,
+                "record of {} byte(s) in a {} byte buffer, expected {}",
+                size, len, EVENT_SIZE
+            ),
+            DecodeError::Unterminated(field) => write!(f, "{} isn't NUL-terminated", field),
+            DecodeError::PathTooLong => write!(
+                f,
+                "path longer than {} bytes, it was likely truncated",
+                MAX_PATH_LEN
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_ne_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+fn str_from(field: &[i8], name: &'static str) -> Result<OsString, DecodeError> {
+    let bytes: Vec<u8> = field.iter().map(|&c| c as u8).collect();
+    CStr::from_bytes_until_nul(&bytes)
+        .map(|s| OsStr::from_bytes(s.to_bytes()).to_os_string())
+        .map_err(|_| DecodeError::Unterminated(name))
+}
+
+/// Decodes a record from the perf buffer, which may be followed by some
+/// padding. Never reads out of `bytes`, whatever they hold.
+pub fn decode(bytes: &[u8]) -> Result<Event, DecodeError> {
+    if bytes.len() < mem::offset_of!(event_t, pid) {
+        return Err(DecodeError::Truncated { len: bytes.len() });
+    }
+    let version = u16_at(bytes, mem::offset_of!(event_t, version));
+    if version != EVENT_VERSION {
+        return Err(DecodeError::Version(version));
+    }
+    let size = u16_at(bytes, mem::offset_of!(event_t, size));
+    if usize::from(size) != EVENT_SIZE || bytes.len() < EVENT_SIZE {
+        return Err(DecodeError::Size {
+            size,
+            len: bytes.len(),
+        });
+    }
+    // SAFETY: there are enough bytes, the read doesn't need them aligned,
+    // and as `event_t` only holds integers any bytes make a valid one.
+    let event = unsafe { ptr::read_unaligned(bytes.as_ptr().cast::<event_t>()) };
+
+    let path = str_from(&event.path, "path")?;
+    if path.len() > MAX_PATH_LEN {
+        return Err(DecodeError::PathTooLong);
+    }
+
+    Ok(Event {
+        pid: event.pid,
+        uid: event.uid,
+        // Truncated by the kernel, so no need to be picky.
+        comm: str_from(&event.comm, "comm")
+            .map(|comm| comm.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        path: path.into(),
+        name: str_from(&event.name, "xattr name")?,
+        value: str_from(&event.value, "xattr value")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A record as the BPF program would send it, its string fields
+    /// NUL-terminated if they fit.
+    fn record(path: &[u8], name: &[u8], value: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![0u8; EVENT_SIZE];
+        bytes[0..2].copy_from_slice(&EVENT_VERSION.to_ne_bytes());
+        bytes[2..4].copy_from_slice(&(EVENT_SIZE as u16).to_ne_bytes());
+        bytes[4..8].copy_from_slice(&42u32.to_ne_bytes());
+        bytes[8..12].copy_from_slice(&1000u32.to_ne_bytes());
+        let mut put = |offset: usize, field: &[u8]| {
+            bytes[offset..offset + field.len()].copy_from_slice(field);
+        };
+        put(mem::offset_of!(event_t, comm), b"attr");
+        put(mem::offset_of!(event_t, path), path);
+        put(mem::offset_of!(event_t, name), name);
+        put(mem::offset_of!(event_t, value), value);
+        bytes
+    }
+
+    fn filled(offset: usize, end: usize) -> Vec<u8> {
+        vec![b'a'; end - offset]
+    }
+
+    #[test]
+    fn decodes_a_valid_record() {
+        let event = decode(&record(b"/tmp/bye", b"user.expire_at", b"1700000000")).unwrap();
+        assert_eq!(
+            event,
+            Event {
+                pid: 42,
+                uid: 1000,
+                comm: "attr".to_string(),
+                path: PathBuf::from("/tmp/bye"),
+                name: OsString::from("user.expire_at"),
+                value: OsString::from("1700000000"),
+            }
+        );
+    }
+
+    #[test]
+    fn ignores_trailing_padding() {
+        let mut bytes = record(b"/tmp/bye", b"user.expire_at", b"1");
+        bytes.extend_from_slice(&[0xff; 4]);
+        assert_eq!(decode(&bytes).unwrap().path, PathBuf::from("/tmp/bye"));
+    }
+
+    #[test]
+    fn refuses_a_short_header() {
+        for len in 0..4 {
+            assert_eq!(
+                decode(&record(b"/", b"", b"")[..len]),
+                Err(DecodeError::Truncated { len })
+            );
+        }
+    }
+
+    #[test]
+    fn refuses_another_version() {
+        let mut bytes = record(b"/tmp/bye", b"user.expire_at", b"1");
+        bytes[0..2].copy_from_slice(&(EVENT_VERSION + 1).to_ne_bytes());
+        assert_eq!(decode(&bytes), Err(DecodeError::Version(EVENT_VERSION + 1)));
+    }
+
+    #[test]
+    fn refuses_a_size_other_than_the_record() {
+        let bytes = record(b"/tmp/bye", b"user.expire_at", b"1");
+        for size in [EVENT_SIZE - 1, EVENT_SIZE + 1, 0, u16::MAX as usize] {
+            let mut bytes = bytes.clone();
+            bytes[2..4].copy_from_slice(&(size as u16).to_ne_bytes());
+            assert_eq!(
+                decode(&bytes),
+                Err(DecodeError::Size {
+                    size: size as u16,
+                    len: EVENT_SIZE
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn refuses_a_buffer_smaller_than_the_record() {
+        let bytes = record(b"/tmp/bye", b"user.expire_at", b"1");
+        for len in [4, 12, EVENT_SIZE - 1] {
+            assert_eq!(
+                decode(&bytes[..len]),
+                Err(DecodeError::Size {
+                    size: EVENT_SIZE as u16,
+                    len
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn refuses_unterminated_strings() {
+        let path = filled(
+            mem::offset_of!(event_t, path),
+            mem::offset_of!(event_t, name),
+        );
+        let name = filled(
+            mem::offset_of!(event_t, name),
+            mem::offset_of!(event_t, value),
+        );
+        let value = filled(mem::offset_of!(event_t, value), EVENT_SIZE);
+        assert_eq!(
+            decode(&record(&path, b"user.expire_at", b"1")),
+            Err(DecodeError::Unterminated("path"))
+        );
+        assert_eq!(
+            decode(&record(b"/tmp/bye", &name, b"1")),
+            Err(DecodeError::Unterminated("xattr name"))
+        );
+        assert_eq!(
+            decode(&record(b"/tmp/bye", b"user.expire_at", &value)),
+            Err(DecodeError::Unterminated("xattr value"))
+        );
+    }
+
+    #[test]
+    fn refuses_a_path_filling_its_field() {
+        let mut path = vec![b'/'];
+        path.resize(MAX_PATH_LEN, b'a');
+        assert_eq!(
+            decode(&record(&path, b"user.expire_at", b"1"))
+                .unwrap()
+                .path,
+            PathBuf::from(OsStr::from_bytes(&path))
+        );
+        path.push(b'a');
+        assert_eq!(
+            decode(&record(&path, b"user.expire_at", b"1")),
+            Err(DecodeError::PathTooLong)
+        );
+    }
+
+    #[test]
+    fn keeps_non_utf8_bytes() {
+        let event = decode(&record(b"/tmp/\xff\xfe", b"user.\x80", b"\xc3")).unwrap();
+        assert_eq!(event.path.as_os_str().as_bytes(), b"/tmp/\xff\xfe");
+        assert_eq!(event.name.as_bytes(), b"user.\x80");
+        assert_eq!(event.value.as_bytes(), b"\xc3");
+    }
+}
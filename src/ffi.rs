@@ -0,0 +1,195 @@
+use crate::client::{self, watched_xattr, ClientError};
+use crate::xattr;
+use libc::c_char;
+use std::ffi::{CStr, OsStr};
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Errors as negative `errno` values, the way C callers expect them.
+fn errno(e: ClientError) -> i32 {
+    match e {
+        ClientError::Io(e) => -e.raw_os_error().unwrap_or(libc::EIO),
+        ClientError::Invalid(_) => -libc::EINVAL,
+        ClientError::Daemon(_) | ClientError::Protocol(_) | ClientError::Timeout => -libc::EIO,
+    }
+}
+
+/// # Safety
+///
+/// `path` must be NULL or point to a NUL-terminated string.
+unsafe fn path<'a>(path: *const c_char) -> Result<&'a Path, ClientError> {
+    if path.is_null() {
+        return Err(ClientError::Invalid("NULL path".to_string()));
+    }
+    let path = unsafe { CStr::from_ptr(path) };
+    Ok(Path::new(OsStr::from_bytes(path.to_bytes())))
+}
+
+/// Makes `path` expire at `epoch`, in seconds, by setting its
+/// `user.expire_at` xattr, or the one named in the config file, which the
+/// running daemon picks up. Relative paths are made absolute.
+///
+/// Returns 0, or a negative `errno`: `-EINVAL` for a path the daemon
+/// couldn't read in full, a timestamp before the epoch or an invalid config
+/// file.
+///
+/// # Safety
+///
+/// `path` must be NULL or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn sweeper_set_expiry(path: *const c_char, epoch: i64) -> i32 {
+    let Ok(secs) = u64::try_from(epoch) else {
+        return -libc::EINVAL;
+    };
+    let at = UNIX_EPOCH + Duration::from_secs(secs);
+    match unsafe { self::path(path) }.and_then(|path| client::expire_at(path, at)) {
+        Ok(_) => 0,
+        Err(e) => errno(e),
+    }
+}
+
+/// Makes `path` expire in `seconds` from now. Returns the timestamp it was
+/// set to, or a negative `errno` like `sweeper_set_expiry`.
+///
+/// # Safety
+///
+/// `path` must be NULL or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn sweeper_set_ttl(path: *const c_char, seconds: u64) -> i64 {
+    match unsafe { self::path(path) }
+        .and_then(|path| client::expire_in(path, Duration::from_secs(seconds)))
+    {
+        Ok(expire_at) => expire_at,
+        Err(e) => i64::from(errno(e)),
+    }
+}
+
+/// Removes the xattr `sweeper_set_expiry` sets from `path`, so the daemon
+/// leaves it alone. Returns 1 if it had one, 0 if it didn't, or a negative
+/// `errno`.
+///
+/// # Safety
+///
+/// `path` must be NULL or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn sweeper_cancel(path: *const c_char) -> i32 {
+    match unsafe { self::path(path) }.and_then(client::clear) {
+        Ok(removed) => i32::from(removed),
+        Err(e) => errno(e),
+    }
+}
+
+/// Reads the xattr `sweeper_set_expiry` sets on `path` the way the daemon
+/// does, storing it in `*epoch`. Returns 1 if it has one, 0 if it doesn't,
+/// or a negative `errno`: `-EINVAL` if the value isn't a timestamp.
+///
+/// # Safety
+///
+/// `path` must be NULL or point to a NUL-terminated string, and `epoch`
+/// must be NULL or point to an `int64_t`.
+#[no_mangle]
+pub unsafe extern "C" fn sweeper_query(path: *const c_char, epoch: *mut i64) -> i32 {
+    if epoch.is_null() {
+        return -libc::EINVAL;
+    }
+    let value = match unsafe { self::path(path) }.and_then(|path| Ok((path, watched_xattr()?))) {
+        Ok((path, name)) => xattr::get(path, &name),
+        Err(e) => return errno(e),
+    };
+    match value {
+        Ok(Some(value)) => match xattr::parse_expire_at(&value) {
+            Some(expire_at) => {
+                unsafe { *epoch = expire_at };
+                1
+            }
+            None => -libc::EINVAL,
+        },
+        Ok(None) => 0,
+        Err(e) => errno(ClientError::Io(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+    use std::fs;
+    use std::ptr;
+    use tempfile::TempDir;
+
+    fn c_path(path: &Path) -> CString {
+        CString::new(path.as_os_str().as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn the_tracked_header_is_up_to_date() {
+        let generated = fs::read_to_string(concat!(env!("OUT_DIR"), "/sweeper.h")).unwrap();
+        let tracked =
+            fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/include/sweeper.h")).unwrap();
+        assert!(
+            generated == tracked,
+            "include/sweeper.h is out of date, copy {}/sweeper.h over it",
+            env!("OUT_DIR")
+        );
+    }
+
+    #[test]
+    fn sets_queries_and_cancels() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("file");
+        fs::write(&path, b"").unwrap();
+        let path = c_path(&path);
+        let mut epoch = 0;
+
+        unsafe {
+            assert_eq!(sweeper_set_expiry(path.as_ptr(), 4102444800), 0);
+            assert_eq!(sweeper_query(path.as_ptr(), &mut epoch), 1);
+            assert_eq!(epoch, 4102444800);
+            assert!(sweeper_set_ttl(path.as_ptr(), 60) > 0);
+            assert_eq!(sweeper_cancel(path.as_ptr()), 1);
+            assert_eq!(sweeper_cancel(path.as_ptr()), 0);
+            assert_eq!(sweeper_query(path.as_ptr(), &mut epoch), 0);
+        }
+    }
+
+    #[test]
+    fn refuses_invalid_arguments() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("file");
+        fs::write(&path, b"").unwrap();
+        let path = c_path(&path);
+        let mut epoch = 0;
+
+        unsafe {
+            assert_eq!(sweeper_set_expiry(ptr::null(), 0), -libc::EINVAL);
+            assert_eq!(sweeper_set_expiry(path.as_ptr(), -1), -libc::EINVAL);
+            assert_eq!(sweeper_set_ttl(ptr::null(), 60), -i64::from(libc::EINVAL));
+            assert_eq!(sweeper_cancel(ptr::null()), -libc::EINVAL);
+            assert_eq!(sweeper_query(ptr::null(), &mut epoch), -libc::EINVAL);
+            assert_eq!(sweeper_query(path.as_ptr(), ptr::null_mut()), -libc::EINVAL);
+        }
+    }
+
+    #[test]
+    fn returns_errno() {
+        let tmp = TempDir::new().unwrap();
+        let missing = c_path(&tmp.path().join("missing"));
+        let garbage = tmp.path().join("garbage");
+        fs::write(&garbage, b"").unwrap();
+        xattr::set(&garbage, &watched_xattr().unwrap(), b"soon").unwrap();
+        let garbage = c_path(&garbage);
+        let mut epoch = 0;
+
+        unsafe {
+            assert_eq!(sweeper_set_expiry(missing.as_ptr(), 0), -libc::ENOENT);
+            assert_eq!(
+                sweeper_set_ttl(missing.as_ptr(), 60),
+                -i64::from(libc::ENOENT)
+            );
+            assert_eq!(sweeper_cancel(missing.as_ptr()), -libc::ENOENT);
+            assert_eq!(sweeper_query(missing.as_ptr(), &mut epoch), -libc::ENOENT);
+            assert_eq!(sweeper_query(garbage.as_ptr(), &mut epoch), -libc::EINVAL);
+        }
+    }
+}
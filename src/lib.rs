@@ -1 +1,25 @@
-pub mod sweeper;
+pub mod action;
+pub mod cleaner;
+pub mod client;
+pub mod config;
+pub mod control;
+pub mod db;
+pub mod error;
+pub mod escape;
+pub mod event;
+pub mod ffi;
+pub mod lock;
+pub mod log;
+pub mod metrics;
+pub mod migrations;
+pub mod quarantine;
+pub mod retry;
+pub mod rules;
+pub mod state;
+pub mod store;
+pub mod sweeper {
+    include!(concat!(env!("OUT_DIR"), "/sweeper.skel.rs"));
+}
+pub mod target;
+pub mod tmpfiles;
+pub mod xattr;
@@ -0,0 +1,46 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+
+/// An exclusive lock on a file, released when dropped or when the process
+/// exits, however it exits.
+pub struct Lock {
+    _file: File,
+}
+
+/// Takes the lock on `path`, creating it if needed, and writes our pid to
+/// it. Fails right away if another process holds it.
+pub fn acquire(path: &Path) -> Result<Lock, String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("{}: {e}", parent.display()))?;
+    }
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .map_err(|e| format!("{}: {e}", path.display()))?;
+
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } != 0 {
+        let err = io::Error::last_os_error();
+        if err.raw_os_error() == Some(libc::EWOULDBLOCK) {
+            let mut pid = String::new();
+            let _ = file.read_to_string(&mut pid);
+            return Err(format!(
+                "{} is locked, another sweeper is running (pid {})",
+                path.display(),
+                pid.trim()
+            ));
+        }
+        return Err(format!("{}: {err}", path.display()));
+    }
+
+    file.set_len(0)
+        .and_then(|_| file.seek(SeekFrom::Start(0)))
+        .and_then(|_| writeln!(file, "{}", std::process::id()))
+        .map_err(|e| format!("{}: {e}", path.display()))?;
+
+    Ok(Lock { _file: file })
+}
@@ -0,0 +1,390 @@
+use crate::escape::escape;
+use serde::Deserialize;
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::io::{self, Write};
+use std::os::unix::fs::MetadataExt;
+use std::os::unix::net::UnixDatagram;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// Where journald listens for its native protocol.
+const JOURNAL_SOCKET: &str = "/run/systemd/journal/socket";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl Level {
+    fn as_str(self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+        }
+    }
+
+    /// As in syslog(3).
+    fn priority(self) -> u8 {
+        match self {
+            Level::Error => 3,
+            Level::Warn => 4,
+            Level::Info => 6,
+            Level::Debug => 7,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    /// Journald when running under systemd, human otherwise.
+    #[default]
+    Auto,
+    /// Only the message, on stdout, or stderr for warnings and errors.
+    Human,
+    /// One object per line on stdout, with the fields next to the message.
+    Json,
+    /// Sent to journald, the fields becoming journal fields.
+    Journald,
+}
+
+/// The value of a field, paths and names escaped like everywhere else.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Str(String),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    /// Unknown, left out of journald records.
+    Null,
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Str(s) => s.fmt(f),
+            Value::Int(n) => n.fmt(f),
+            Value::Float(n) => n.fmt(f),
+            Value::Bool(b) => b.fmt(f),
+            Value::Null => Ok(()),
+        }
+    }
+}
+
+impl From<&Value> for serde_json::Value {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Str(s) => s.as_str().into(),
+            Value::Int(n) => (*n).into(),
+            Value::Float(n) => (*n).into(),
+            Value::Bool(b) => (*b).into(),
+            Value::Null => serde_json::Value::Null,
+        }
+    }
+}
+
+macro_rules! value_from {
+    ($variant:ident: $($ty:ty),+) => {
+        $(impl From<$ty> for Value {
+            fn from(value: $ty) -> Self {
+                Value::$variant(value.into())
+            }
+        })+
+    };
+}
+
+value_from!(Int: i32, i64, u8, u16, u32);
+value_from!(Bool: bool);
+value_from!(Str: &str, String);
+
+impl From<u64> for Value {
+    fn from(value: u64) -> Self {
+        Value::Int(value.try_into().unwrap_or(i64::MAX))
+    }
+}
+
+impl From<usize> for Value {
+    fn from(value: usize) -> Self {
+        Value::from(value as u64)
+    }
+}
+
+impl From<&String> for Value {
+    fn from(value: &String) -> Self {
+        Value::Str(value.clone())
+    }
+}
+
+impl From<&Path> for Value {
+    fn from(value: &Path) -> Self {
+        Value::Str(escape(value).to_string())
+    }
+}
+
+impl From<&PathBuf> for Value {
+    fn from(value: &PathBuf) -> Self {
+        Value::from(value.as_path())
+    }
+}
+
+impl From<&OsStr> for Value {
+    fn from(value: &OsStr) -> Self {
+        Value::Str(escape(value).to_string())
+    }
+}
+
+impl From<&OsString> for Value {
+    fn from(value: &OsString) -> Self {
+        Value::from(value.as_os_str())
+    }
+}
+
+/// In seconds.
+impl From<Duration> for Value {
+    fn from(value: Duration) -> Self {
+        Value::Float(value.as_secs_f64())
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Value::Null, Into::into)
+    }
+}
+
+pub type Fields = Vec<(&'static str, Value)>;
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Info as u8);
+static FORMAT: AtomicU8 = AtomicU8::new(Format::Human as u8);
+
+/// Sets the level and format of what follows, again on reload.
+pub fn init(level: Level, format: Format) {
+    let format = match format {
+        Format::Auto if under_journald() => Format::Journald,
+        Format::Auto => Format::Human,
+        format => format,
+    };
+    LEVEL.store(level as u8, Ordering::Relaxed);
+    FORMAT.store(format as u8, Ordering::Relaxed);
+}
+
+pub fn enabled(level: Level) -> bool {
+    level as u8 <= LEVEL.load(Ordering::Relaxed)
+}
+
+fn format() -> Format {
+    match FORMAT.load(Ordering::Relaxed) {
+        f if f == Format::Json as u8 => Format::Json,
+        f if f == Format::Journald as u8 => Format::Journald,
+        _ => Format::Human,
+    }
+}
+
+/// systemd sets `JOURNAL_STREAM` to the device and inode of the journal
+/// stream stdout or stderr are connected to, see systemd.exec(5). It's
+/// inherited by children whose output goes elsewhere, hence the check.
+fn under_journald() -> bool {
+    let Some(stream) = std::env::var_os("JOURNAL_STREAM") else {
+        return false;
+    };
+    [Path::new("/proc/self/fd/1"), Path::new("/proc/self/fd/2")]
+        .iter()
+        .filter_map(|fd| std::fs::metadata(fd).ok())
+        .any(|metadata| {
+            stream.to_string_lossy() == format!("{}:{}", metadata.dev(), metadata.ino())
+        })
+}
+
+/// Machine formats get the message without its emoji and tree prefix, the
+/// human format as it is.
+fn bare(message: &str) -> &str {
+    message.trim_start_matches(|c: char| !c.is_alphanumeric())
+}
+
+/// Writes a record in the current format. Journald falling over, the record
+/// is written to stderr instead, for whoever is listening there.
+pub fn emit(level: Level, fields: &[(&'static str, Value)], message: fmt::Arguments) {
+    if !enabled(level) {
+        return;
+    }
+    let message = message.to_string();
+    let result = match format() {
+        Format::Json => write_json(level, fields, &message),
+        Format::Journald => send_journald(level, fields, &message),
+        _ => write_human(level, &message),
+    };
+    if result.is_err() {
+        let _ = write_human(Level::Error, &message);
+    }
+}
+
+fn write_human(level: Level, message: &str) -> io::Result<()> {
+    if level <= Level::Warn {
+        writeln!(io::stderr().lock(), "{}", message)
+    } else {
+        writeln!(io::stdout().lock(), "{}", message)
+    }
+}
+
+fn write_json(level: Level, fields: &[(&'static str, Value)], message: &str) -> io::Result<()> {
+    let ts = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64();
+    // Written at once so lines from different threads don't interleave.
+    let line = format!("{}\n", json_record(ts, level, fields, message));
+    io::stdout().lock().write_all(line.as_bytes())
+}
+
+fn json_record(
+    ts: f64,
+    level: Level,
+    fields: &[(&'static str, Value)],
+    message: &str,
+) -> serde_json::Value {
+    let mut record = serde_json::Map::new();
+    record.insert("ts".to_string(), ts.into());
+    record.insert("level".to_string(), level.as_str().into());
+    record.insert("message".to_string(), bare(message).into());
+    for (key, value) in fields {
+        record.insert(key.to_string(), value.into());
+    }
+    serde_json::Value::Object(record)
+}
+
+fn send_journald(level: Level, fields: &[(&'static str, Value)], message: &str) -> io::Result<()> {
+    static SOCKET: OnceLock<io::Result<UnixDatagram>> = OnceLock::new();
+    let socket = SOCKET
+        .get_or_init(UnixDatagram::unbound)
+        .as_ref()
+        .map_err(|e| io::Error::new(e.kind(), e.to_string()))?;
+    let datagram = journald_datagram(level, fields, message);
+    socket.send_to(&datagram, JOURNAL_SOCKET).map(|_| ())
+}
+
+/// Uses the native protocol, where fields are `KEY=value` lines, or the key,
+/// the length and the value for values spanning several lines.
+fn journald_datagram(level: Level, fields: &[(&'static str, Value)], message: &str) -> Vec<u8> {
+    let mut datagram = Vec::new();
+    let mut add = |key: &str, value: &str| {
+        datagram.extend_from_slice(key.as_bytes());
+        if value.contains('\n') {
+            datagram.push(b'\n');
+            datagram.extend_from_slice(&(value.len() as u64).to_le_bytes());
+        } else {
+            datagram.push(b'=');
+        }
+        datagram.extend_from_slice(value.as_bytes());
+        datagram.push(b'\n');
+    };
+    add("MESSAGE", bare(message));
+    add("PRIORITY", &level.priority().to_string());
+    add("SYSLOG_IDENTIFIER", "sweeper");
+    for (key, value) in fields.iter().filter(|(_, value)| *value != Value::Null) {
+        add(&key.to_ascii_uppercase(), &value.to_string());
+    }
+    datagram
+}
+
+/// Logs at `level`, with optional `key = value` fields, or a `Fields`
+/// followed by `=>`, before the message:
+/// `info!(path = &path, uid = uid; "🧹 Scheduled {}", escape(&path))`.
+/// Nothing is evaluated if the level is disabled.
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($key:ident = $value:expr),+ ; $($arg:tt)+) => {
+        if $crate::log::enabled($level) {
+            $crate::log::emit(
+                $level,
+                &[$((stringify!($key), $crate::log::Value::from($value))),+],
+                format_args!($($arg)+),
+            )
+        }
+    };
+    ($level:expr, $fields:expr => $($arg:tt)+) => {
+        if $crate::log::enabled($level) {
+            $crate::log::emit($level, &$fields, format_args!($($arg)+))
+        }
+    };
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::log::enabled($level) {
+            $crate::log::emit($level, &[], format_args!($($arg)+))
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Error, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Warn, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Info, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Debug, $($arg)+) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_json_records() {
+        let fields = [
+            ("path", Value::from(Path::new("/tmp/caf\u{e9}"))),
+            ("pid", Value::from(42u32)),
+            ("latency", Value::from(Duration::from_millis(1500))),
+            ("action", Value::from(None::<&str>)),
+        ];
+        let record = json_record(12.5, Level::Warn, &fields, "🧹 Freed 3 byte(s)");
+
+        assert_eq!(
+            record,
+            serde_json::json!({
+                "ts": 12.5,
+                "level": "warn",
+                "message": "Freed 3 byte(s)",
+                "path": "/tmp/caf\u{e9}",
+                "pid": 42,
+                "latency": 1.5,
+                "action": null,
+            })
+        );
+    }
+
+    #[test]
+    fn encodes_journald_fields() {
+        let fields = [
+            ("path", Value::from(Path::new("/tmp/bye"))),
+            ("outcome", Value::from("deleted")),
+            ("comm", Value::from(None::<&str>)),
+        ];
+        let datagram = journald_datagram(Level::Error, &fields, "❌ Couldn't\nsweep");
+
+        let mut expected = b"MESSAGE\n".to_vec();
+        expected.extend_from_slice(&14u64.to_le_bytes());
+        expected.extend_from_slice(b"Couldn't\nsweep\n");
+        expected.extend_from_slice(
+            b"PRIORITY=3\nSYSLOG_IDENTIFIER=sweeper\nPATH=/tmp/bye\nOUTCOME=deleted\n",
+        );
+        assert_eq!(datagram, expected);
+    }
+}
@@ -1,242 +1,1361 @@
-use core::sync::atomic::{AtomicBool, Ordering};
-use libbpf_rs::{PerfBufferBuilder};
-use rusqlite::{params, Connection, Result};
-use std::ffi::CStr;
-use std::fs;
-use std::os::raw::c_char;
-use std::ptr;
-use std::time::Duration;
-use std::{thread, time};
+use clap::{Args, Parser, Subcommand};
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use libbpf_rs::PerfBufferBuilder;
+use serde::{Deserialize, Serialize};
+use signal_hook::consts::{SIGHUP, SIGINT, SIGTERM};
+use std::collections::{BTreeSet, HashMap};
+use std::ffi::{OsStr, OsString};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use sweeper::cleaner::{
+    clean_up, current, recover, replace, requeue_dry_runs, shared, Shared, RULE_PREFIX,
+};
+use sweeper::client::{self, Client, ClientError};
+use sweeper::config::{Backend, Config, Settings, Source, CONFIG_PATH};
+use sweeper::control::{
+    self, Cancelled, Extended, Hub, Peer, Reloaded, Request, Response, Shown, PROTOCOL_VERSION,
+};
+use sweeper::error::SweeperError;
+use sweeper::escape::{self, escape};
+use sweeper::event::{self, Event};
+use sweeper::lock;
+use sweeper::log;
+use sweeper::metrics::IngestMetrics;
+use sweeper::quarantine;
+use sweeper::rules::{load_rules, parse_age, Rule, RULES_DIR};
+use sweeper::state::State;
+use sweeper::store::{
+    self, epoch, Entry, MemoryStore, ObservedStore, SqliteStore, Store, Transition, Update,
+};
 use sweeper::sweeper::SweeperSkelBuilder;
+use sweeper::tmpfiles::{load_tmpfiles, TMPFILES_DIRS};
+use sweeper::xattr;
+use sweeper::{debug, error, info, warn};
 
-use std::sync::mpsc::channel;
-use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{sync_channel, Receiver, RecvTimeoutError, SyncSender};
+use std::sync::{Arc, Mutex, MutexGuard};
 
-#[repr(C)]
-struct event_t {
-    path: [u8; 50],
-    name: [u8; 50],
-    value: [u8; 50],
-}
+// How long `set --confirm` waits for the daemon.
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Longest line accepted on the control socket.
+const MAX_REQUEST_SIZE: usize = 64 * 1024;
+
+// Control clients served at once, each getting a thread.
+const MAX_CLIENTS: usize = 64;
+
+// How long a control client may take to send a request or read an answer.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(10);
 
-#[derive(Debug)]
-struct Event {
-    id: Option<i32>,
-    path: String,
-    name: String,
-    expire_at: i32,
+/// Flags raised by the signals the daemon handles.
+struct Signals {
+    /// Cleared on SIGINT or SIGTERM.
+    runnable: Arc<AtomicBool>,
+    /// Set on SIGHUP.
+    reload: Arc<AtomicBool>,
 }
 
 struct Sweeper {
-    conn: Arc<Mutex<Connection>>,
-    cleaner_conn: Arc<Mutex<Connection>>,
+    store: Arc<Mutex<Box<dyn Store>>>,
+    cleaner_store: Arc<Mutex<Box<dyn Store>>>,
+    control_store: Arc<Mutex<Box<dyn Store>>>,
+    hub: Arc<Hub>,
     runnable: Arc<AtomicBool>,
-    sender: std::sync::mpsc::Sender<Event>,
-    receiver: Arc<Mutex<std::sync::mpsc::Receiver<Event>>>,
-    threads: Vec<std::thread::JoinHandle<()>>,
+    config: Shared<Config>,
+    source: Arc<Source>,
+    rules: Shared<Vec<Rule>>,
+    /// Set to scan the rules and expire what's due without waiting.
+    sweep: Arc<AtomicBool>,
+    /// Set on SIGHUP to load the config and the rules again.
+    reload: Arc<AtomicBool>,
+    dry_run: bool,
+    metrics: Arc<IngestMetrics>,
+    threads: Vec<std::thread::JoinHandle<Result<(), SweeperError>>>,
+}
+
+/// Opens the database, migrating its schema if needed.
+fn open_db(config: &Config) -> SqliteStore {
+    SqliteStore::open(&config.db_path).unwrap_or_else(|e| {
+        eprintln!("Error: {}: {}", escape(&config.db_path), e);
+        std::process::exit(1);
+    })
+}
+
+/// Opens the store of the daemon, once for ingestion, once for the
+/// cleaner and once for the control socket, each reporting to `hub`.
+fn open_stores(config: &Config, hub: &Arc<Hub>) -> [Box<dyn Store>; 3] {
+    let memory = MemoryStore::new();
+    let open = || -> Box<dyn Store> {
+        let store: Box<dyn Store> = match config.store {
+            Backend::Sqlite => Box::new(open_db(config)),
+            Backend::Memory => Box::new(memory.clone()),
+        };
+        Box::new(ObservedStore::new(store, hub.clone()))
+    };
+    [open(), open(), open()]
 }
 
 impl Sweeper {
     pub fn new(
-        connection: Connection,
-        cleaner_connection: Connection,
-        runnable: Arc<AtomicBool>,
+        config: Config,
+        source: Source,
+        signals: &Signals,
+        rules: Vec<Rule>,
+        dry_run: bool,
     ) -> Self {
-        let (sender, receiver) = channel();
+        let hub = Arc::new(Hub::default());
+        let [store, cleaner_store, control_store] = open_stores(&config, &hub);
         Sweeper {
-            conn: Arc::new(Mutex::new(connection)),
-            cleaner_conn: Arc::new(Mutex::new(cleaner_connection)),
-            runnable,
-            sender,
-            receiver: Arc::new(Mutex::new(receiver)),
+            store: Arc::new(Mutex::new(store)),
+            cleaner_store: Arc::new(Mutex::new(cleaner_store)),
+            control_store: Arc::new(Mutex::new(control_store)),
+            hub,
+            runnable: signals.runnable.clone(),
+            config: shared(config),
+            source: Arc::new(source),
+            rules: shared(rules),
+            sweep: Arc::new(AtomicBool::new(false)),
+            reload: signals.reload.clone(),
+            dry_run,
+            metrics: Arc::new(IngestMetrics::default()),
             threads: Vec::new(),
         }
     }
-    pub fn setup_db(&self) {
-        self.conn
-            .lock()
-            .unwrap()
-            .execute(
-                "CREATE TABLE IF NOT EXISTS sweeper (
-            id INTEGER PRIMARY KEY,
-            path TEXT NOT NULL,
-            name TEXT NOT NULL,
-            expire_at timestamp NOT NULL)",
-                params![],
-            )
-            .unwrap();
+    pub fn setup_db(&self) -> Result<(), SweeperError> {
+        let store = lock_store(&self.store);
+        recover(store.as_ref())?;
+        if !self.dry_run {
+            let requeued = requeue_dry_runs(store.as_ref())?;
+            if requeued > 0 {
+                info!("🧪 {} file(s) skipped by a dry run are due again", requeued);
+            }
+        }
+        Ok(())
     }
 
     pub fn setup_cleaner(&mut self) {
         let runnable = self.runnable.clone();
-        let conn = self.cleaner_conn.clone();
+        let store = self.cleaner_store.clone();
+        let config = self.config.clone();
+        let rules = self.rules.clone();
+        let sweep = self.sweep.clone();
+        let dry_run = self.dry_run;
 
-        let t = thread::spawn(move || clean_up(conn, runnable));
+        let t = thread::spawn(move || {
+            let store = lock_store(&store);
+            clean_up(store.as_ref(), &config, &rules, &sweep, dry_run, &runnable);
+            Ok(())
+        });
         self.threads.push(t);
     }
 
-    fn process(&mut self) {
-        let recv = self.receiver.clone();
-        let runnable = self.runnable.clone();
-        let conn = self.conn.clone();
+    /// Listens on the control socket, replacing the one a previous run may
+    /// have left behind: we hold the lock, so nobody else is using it.
+    pub fn setup_control(&mut self) -> Result<(), SweeperError> {
+        let socket = current(&self.config).socket.clone();
+        let error = |e: io::Error| SweeperError::Control(format!("{}: {}", escape(&socket), e));
+        match fs::remove_file(&socket) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(error(e)),
+            _ => {}
+        }
+        let listener = UnixListener::bind(&socket).map_err(error)?;
+        // Anyone can connect, what they may do depends on who they are.
+        fs::set_permissions(&socket, fs::Permissions::from_mode(0o666)).map_err(error)?;
+        listener.set_nonblocking(true).map_err(error)?;
+        info!(socket = &socket; "🔌 Listening on {}", escape(&socket));
+
+        let control = Control {
+            store: self.control_store.clone(),
+            hub: self.hub.clone(),
+            config: self.config.clone(),
+            source: self.source.clone(),
+            rules: self.rules.clone(),
+            sweep: self.sweep.clone(),
+            reload: self.reload.clone(),
+            runnable: self.runnable.clone(),
+            uid: unsafe { libc::geteuid() },
+            clients: Arc::new(AtomicUsize::new(0)),
+        };
+        let t = thread::spawn(move || {
+            let result = control.listen(listener);
+            let _ = fs::remove_file(&socket);
+            result
+        });
+        self.threads.push(t);
+        Ok(())
+    }
+
+    /// Writes the events to the store until every sender is gone, so what
+    /// was queued before shutting down isn't lost.
+    fn process(&mut self, recv: Receiver<Entry>) {
+        let store = self.store.clone();
+        let config = self.config.clone();
+        let metrics = self.metrics.clone();
 
         let t = thread::spawn(move || {
-            let conn = conn.lock().unwrap();
+            let store = lock_store(&store);
+            let mut batch = Vec::new();
+            let mut last_report = Instant::now();
 
-            while runnable.load(Ordering::SeqCst) {
-                if let Ok(event) = recv.lock().unwrap().try_recv() {
-                    conn.execute(
-                        "INSERT INTO sweeper (path, name, expire_at) VALUES (?1, ?2, ?3)",
-                        params![event.path, event.name, event.expire_at],
-                    )
-                    .unwrap();
+            loop {
+                let limits = current(&config).limits.clone();
+                if last_report.elapsed() >= Duration::from_secs(limits.metrics_interval) {
+                    info!(depth = metrics.depth(); "📊 {}", metrics);
+                    last_report = Instant::now();
                 }
 
-                thread::sleep(time::Duration::from_millis(100));
+                // Wait for an event, then take whatever else is already
+                // queued, so a burst is written in a few transactions.
+                match recv.recv_timeout(Duration::from_millis(200)) {
+                    Ok(event) => batch.push(event),
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+                while batch.len() < limits.ingest_batch {
+                    match recv.try_recv() {
+                        Ok(event) => batch.push(event),
+                        Err(_) => break,
+                    }
+                }
+
+                for event in &mut batch {
+                    if let Ok(metadata) = fs::symlink_metadata(&event.path) {
+                        event.dev = Some(metadata.dev());
+                        event.ino = Some(metadata.ino());
+                    }
+                }
+                // Setting the xattr again replaces the previous expiration.
+                match store.ingest(&batch, "superseded") {
+                    Ok(_) => metrics.ingested(batch.len() as u64),
+                    Err(e) => {
+                        error!(
+                            count = batch.len();
+                            "❌ Couldn't record {} event(s): {}", batch.len(), e
+                        );
+                        metrics.failed(batch.len() as u64);
+                    }
+                }
+                batch.clear();
             }
+            Ok(())
         });
 
         self.threads.push(t);
     }
-    pub fn run(mut self) -> Result<(), &'static str> {
-        self.setup_db();
+
+    pub fn run(mut self) -> Result<(), SweeperError> {
+        let (sender, receiver) = sync_channel(current(&self.config).limits.channel_capacity);
+        self.setup_db()?;
+        self.setup_control()?;
         self.setup_cleaner();
-        self.process();
-        // todo: propagate BccError
-        self.run_bpf(); // .unwrap();
-        self.join_threads();
+        self.process(receiver);
+        let result = self.run_bpf(sender);
 
-        Ok(())
+        // The sender is gone with `run_bpf`, the ingestion thread stops once
+        // it has written what's left in the channel, and the cleaner after
+        // the expiration it's working on.
+        self.runnable.store(false, Ordering::SeqCst);
+        let started = Instant::now();
+        info!(
+            depth = self.metrics.depth();
+            "🛑 Shutting down, {} event(s) left to record",
+            self.metrics.depth()
+        );
+        let timeout = current(&self.config).shutdown_timeout;
+        thread::spawn(move || {
+            thread::sleep(timeout);
+            error!(
+                "Error: still shutting down after {:?}, giving up. Interrupted expirations are rolled back on the next start",
+                timeout
+            );
+            std::process::exit(1);
+        });
+        let metrics = self.metrics.clone();
+        let joined = self.join_threads();
+        info!(
+            latency = started.elapsed();
+            "👋 Stopped in {:.1?}: {}", started.elapsed(), metrics
+        );
+
+        result.and(joined)
     }
 
-    pub fn join_threads(self) {
+    /// Waits for every thread, returning the first error one of them hit.
+    pub fn join_threads(self) -> Result<(), SweeperError> {
+        let mut result = Ok(());
         for thread in self.threads {
-            if let Err(e) = thread.join() {
-                eprintln!("Error: {:?}", e);
-                std::process::exit(1);
+            let outcome = thread.join().unwrap_or_else(|e| {
+                let message = e
+                    .downcast_ref::<&str>()
+                    .map(|s| s.to_string())
+                    .or_else(|| e.downcast_ref::<String>().cloned())
+                    .unwrap_or_default();
+                Err(SweeperError::Thread(message))
+            });
+            if result.is_ok() {
+                result = outcome;
             }
         }
+        result
     }
 
-    pub fn run_bpf(&self) {
+    pub fn run_bpf(&self, sender: SyncSender<Entry>) -> Result<(), SweeperError> {
         let skel_builder = SweeperSkelBuilder::default();
-        let open_skel = skel_builder.open().unwrap();
-        let mut bpf = open_skel.load().expect("bpf load");
+        let open_skel = skel_builder
+            .open()
+            .map_err(SweeperError::bpf("opening the bpf object"))?;
+        let mut bpf = open_skel
+            .load()
+            .map_err(SweeperError::bpf("loading the bpf program"))?;
 
         let perf_buffer = PerfBufferBuilder::new(bpf.maps().events())
             .sample_cb(|_cpu: i32, data: &[u8]| {
-                self.on_event(data);
+                if let Err(e) = self.on_event(&sender, data) {
+                    warn!("╰ ❌ {}", e);
+                    if matches!(e, SweeperError::Parse(_)) {
+                        self.metrics.invalid();
+                    }
+                }
             })
             .lost_cb(|cpu, count| {
-                eprintln!("Lost {} events on cpu {}", count, cpu)
+                warn!(count = count, cpu = cpu; "Lost {} events on cpu {}", count, cpu);
+                self.metrics.lost(count);
             })
-            .build().expect("perf buffer build");
+            .build()
+            .map_err(SweeperError::bpf("building the perf buffer"))?;
 
-        bpf.attach().expect("attach bpf program");
+        bpf.attach()
+            .map_err(SweeperError::bpf("attaching the bpf program"))?;
 
         let timeout: Duration = Duration::from_millis(200);
         while self.runnable.load(Ordering::SeqCst) {
-            perf_buffer.poll(timeout).expect("perf buffer poll");
+            perf_buffer
+                .poll(timeout)
+                .map_err(SweeperError::bpf("polling the perf buffer"))?;
+        }
+        // What the kernel already handed over still gets recorded.
+        perf_buffer
+            .consume()
+            .map_err(SweeperError::bpf("draining the perf buffer"))
+    }
+
+    fn on_event(&self, sender: &SyncSender<Entry>, x: &[u8]) -> Result<(), SweeperError> {
+        let Event {
+            pid,
+            uid,
+            comm,
+            path,
+            name,
+            value,
+        } = event::decode(x)?;
+        let config = current(&self.config);
+        let log = config.log.events;
+
+        if log {
+            debug!(
+                path = &path, name = &name, pid = pid, uid = uid, comm = &comm;
+                "📅 Entry: (path={}, name={}, value={})",
+                escape(&path),
+                escape(&name),
+                escape(&value)
+            );
+        }
+
+        if name.as_bytes() != config.xattr.expire_at.as_bytes() {
+            if log {
+                debug!("╰ 😴 setattr's name should be `{}`", config.xattr.expire_at);
+            }
+            return Ok(());
         }
+        if !path.is_absolute() {
+            if log {
+                debug!("╰ 🚮 Path must be absolute");
+            }
+            return Ok(());
+        }
+        if !config.watches(&path) {
+            if log {
+                debug!("╰ 😴 Not under a watched root");
+            }
+            return Ok(());
+        }
+        let expire_at = xattr::parse_expire_at(value.as_bytes()).ok_or_else(|| {
+            SweeperError::Parse(format!(
+                "{} should be a timestamp, not `{}`",
+                config.xattr.expire_at,
+                escape(&value)
+            ))
+        })?;
+
+        if log {
+            info!(
+                path = &path, pid = pid, uid = uid, comm = &comm, deadline = expire_at;
+                "🧹 Scheduled {} for deletion at {}", escape(&path), expire_at
+            );
+        }
+        // Waits while the channel is full, only fails if the ingestion
+        // thread is gone.
+        self.metrics.queued();
+        sender
+            .send(Entry {
+                id: None,
+                path,
+                name: config.xattr.expire_at.clone(),
+                expire_at,
+                pid: Some(pid),
+                uid: Some(uid),
+                comm: Some(comm),
+                ..Entry::default()
+            })
+            .map_err(|_| SweeperError::Thread("the ingestion thread is gone".to_string()))
     }
+}
 
-    fn on_event(&self, x: &[u8]) {
-        println!("EVENT");
-        let tx = self.sender.clone();
-        unsafe {
-            let data = ptr::read(x.as_ptr() as *const event_t);
+/// The stores are only shared to hand them over to their thread, nothing
+/// else holds the lock.
+fn lock_store(store: &Mutex<Box<dyn Store>>) -> MutexGuard<'_, Box<dyn Store>> {
+    store.lock().unwrap_or_else(|e| e.into_inner())
+}
 
-            let path = CStr::from_ptr(data.path.as_ptr() as *const c_char)
-                .to_str()
-                .unwrap();
-            let name = CStr::from_ptr(data.name.as_ptr() as *const c_char)
-                .to_str()
-                .unwrap();
-            let value = CStr::from_ptr(data.value.as_ptr() as *const c_char)
-                .to_str()
-                .unwrap();
+/// Every expiration of `path`, most recent first, with their history.
+fn shown(store: &dyn Store, path: &Path) -> store::Result<Vec<Shown>> {
+    store
+        .find(path)?
+        .into_iter()
+        .map(|entry| {
+            Ok(Shown {
+                history: store.history(entry.id.unwrap_or_default())?,
+                entry,
+            })
+        })
+        .collect()
+}
 
-            println!("📅 Event: (path={}, name={}, value={})", path, name, value);
+/// Opens `path` for `cancel_file` and `extend`, `None` if it's gone.
+fn open_target(path: &Path) -> Result<Option<File>, String> {
+    match xattr::open(path) {
+        Ok(file) => Ok(Some(file)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("{}: {}", escape(path), e)),
+    }
+}
 
-            if name == "user.expire_at" {
-                if &path[0..1] == "/" {
-                    println!("╰ 🧹 Scheduled for deletion");
-                    tx.send(Event {
-                        id: None,
-                        path: path.to_string(),
-                        name: name.to_string(),
-                        expire_at: value.parse::<i32>().unwrap(),
-                    })
-                    .unwrap();
-                } else {
-                    println!("╰ 🚮 Path must be absolute");
+/// Cancels the pending expirations of `path` and removes the xattrs that
+/// set them, along with `expire_at`, the one currently watched, from
+/// `file`, as opened by `open_target`. Returns how many were cancelled,
+/// failing if there was nothing to cancel.
+fn cancel_file(
+    store: &dyn Store,
+    path: &Path,
+    file: Option<&File>,
+    expire_at: &str,
+) -> Result<usize, String> {
+    let names: BTreeSet<String> = store
+        .find(path)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|entry| entry.state == State::Pending)
+        .map(|entry| entry.name)
+        .collect();
+    let mut cancelled = 0;
+    for name in &names {
+        cancelled += store
+            .cancel(path, name, "cancelled by hand")
+            .map_err(|e| e.to_string())?;
+    }
+    // Otherwise the daemon would find it again when verifying the file.
+    let xattrs = names
+        .iter()
+        .map(String::as_str)
+        .filter(|name| !name.starts_with(RULE_PREFIX))
+        .chain([expire_at])
+        .collect::<BTreeSet<_>>();
+    let mut removed = false;
+    // Gone, it has no xattrs left to remove.
+    if let Some(file) = file {
+        for name in xattrs {
+            removed |=
+                xattr::remove_fd(file, name).map_err(|e| format!("{}: {}", escape(path), e))?;
+        }
+    }
+
+    if cancelled == 0 && !removed {
+        return Err(format!("{} isn't scheduled to expire", escape(path)));
+    }
+    Ok(cancelled)
+}
+
+/// Pushes the `user.expire_at` of `path`, or whichever xattr set its
+/// expiration, back by `by`, returning the new deadline. The xattr is set
+/// on `file`, as opened by `open_target`. The daemon sees the change like
+/// any other, which supersedes the current expiration.
+fn extend(
+    store: &dyn Store,
+    path: &Path,
+    file: Option<&File>,
+    by: Duration,
+) -> Result<i64, String> {
+    let pending: Vec<Entry> = store
+        .find(path)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|entry| entry.state == State::Pending)
+        .collect();
+    let Some(entry) = pending
+        .iter()
+        .find(|entry| !entry.name.starts_with(RULE_PREFIX))
+    else {
+        return Err(match pending.first() {
+            // The deadline comes from the age of the file, the next scan
+            // would put it back.
+            Some(entry) => format!(
+                "{} expires through `{}`, which can't be extended",
+                escape(path),
+                entry.name
+            ),
+            None => format!("{} isn't scheduled to expire", escape(path)),
+        });
+    };
+
+    let Some(file) = file else {
+        return Err(format!("{} doesn't exist anymore", escape(path)));
+    };
+    let by = i64::try_from(by.as_secs()).unwrap_or(i64::MAX);
+    let expire_at = entry.expire_at.saturating_add(by);
+    xattr::set_fd(file, &entry.name, expire_at.to_string().as_bytes())
+        .map_err(|e| format!("{}: {}", escape(path), e))?;
+    Ok(expire_at)
+}
+
+/// Loads the rules from `RULES_DIR` and the tmpfiles.d age directives.
+fn load_all_rules() -> Result<Vec<Rule>, SweeperError> {
+    let mut rules = load_rules(Path::new(RULES_DIR)).map_err(SweeperError::Rules)?;
+    info!("📏 Loaded {} rule(s) from {}", rules.len(), RULES_DIR);
+
+    let (tmpfiles_rules, warnings) = load_tmpfiles(&TMPFILES_DIRS);
+    for warning in warnings {
+        warn!("tmpfiles.d: {}", warning);
+    }
+    info!(
+        "📏 Loaded {} tmpfiles.d age directive(s)",
+        tmpfiles_rules.len()
+    );
+    rules.extend(tmpfiles_rules);
+    Ok(rules)
+}
+
+/// Serves the control socket. Every connection gets its own thread, up to
+/// `MAX_CLIENTS` of them, which answers one request per line until the
+/// client hangs up or is idle for too long.
+#[derive(Clone)]
+struct Control {
+    store: Arc<Mutex<Box<dyn Store>>>,
+    hub: Arc<Hub>,
+    config: Shared<Config>,
+    source: Arc<Source>,
+    rules: Shared<Vec<Rule>>,
+    sweep: Arc<AtomicBool>,
+    reload: Arc<AtomicBool>,
+    runnable: Arc<AtomicBool>,
+    /// Who the daemon runs as, trusted like root.
+    uid: u32,
+    /// Connections being served.
+    clients: Arc<AtomicUsize>,
+}
+
+/// Counts a connection as served until dropped.
+struct Served(Arc<AtomicUsize>);
+
+impl Drop for Served {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl Control {
+    /// Also reloads the config on SIGHUP, which can't be done from the
+    /// signal handler itself.
+    fn listen(&self, listener: UnixListener) -> Result<(), SweeperError> {
+        while self.runnable.load(Ordering::SeqCst) {
+            if self.reload.swap(false, Ordering::SeqCst) {
+                info!("🔄 Reloading on SIGHUP");
+                match self.reload_config() {
+                    Ok(reloaded) => info!("🔄 Reloaded, {} rule(s)", reloaded.rules),
+                    Err(e) => error!("❌ Couldn't reload, keeping the current config: {}", e),
+                }
+            }
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    // Only this thread adds to it, so it can't go over.
+                    if self.clients.load(Ordering::SeqCst) >= MAX_CLIENTS {
+                        warn!("⚠️  Too many control clients, turning one away");
+                        let _ = send(&mut &stream, &Response::error("too many clients"));
+                        continue;
+                    }
+                    self.clients.fetch_add(1, Ordering::SeqCst);
+                    let served = Served(self.clients.clone());
+                    let control = self.clone();
+                    thread::spawn(move || {
+                        let _served = served;
+                        control.serve(stream)
+                    });
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                    thread::sleep(Duration::from_millis(100));
+                }
+                Err(e) => return Err(SweeperError::Control(format!("accepting: {}", e))),
+            }
+        }
+        Ok(())
+    }
+
+    fn serve(&self, stream: UnixStream) {
+        let peer = match control::peer(&stream) {
+            Ok(peer) => peer,
+            Err(e) => {
+                warn!("❌ Couldn't identify a control client: {}", e);
+                return;
+            }
+        };
+        let _ = stream.set_nonblocking(false);
+        // Clients sending nothing or not reading their answers are let go.
+        // Subscribers aren't read from anymore once subscribed.
+        let _ = stream.set_read_timeout(Some(CLIENT_TIMEOUT));
+        let _ = stream.set_write_timeout(Some(CLIENT_TIMEOUT));
+        let mut reader = BufReader::new(&stream);
+        let mut writer = &stream;
+        let mut line = Vec::new();
+
+        loop {
+            line.clear();
+            match (&mut reader)
+                .take(MAX_REQUEST_SIZE as u64 + 1)
+                .read_until(b'\n', &mut line)
+            {
+                Ok(0) | Err(_) => return,
+                Ok(_) => {}
+            }
+            if line.len() > MAX_REQUEST_SIZE {
+                let _ = send(&mut writer, &Response::error("request too large"));
+                return;
+            }
+
+            let response = match parse_request(&line) {
+                Ok(Request {
+                    command: control::Command::Subscribe,
+                    ..
+                }) => match self.authorize(&peer, &control::Command::Subscribe) {
+                    Ok(_) => return self.stream(&mut writer),
+                    Err(e) => Response::error(e),
+                },
+                Ok(request) => match self.authorize(&peer, &request.command) {
+                    Ok(file) => self.handle(&peer, request.command, file.as_ref()),
+                    Err(e) => Response::error(e),
+                },
+                Err(e) => Response::error(e),
+            };
+            if send(&mut writer, &response).is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Loads the config and the rules again, keeping the current ones if
+    /// either is invalid. The BPF program stays attached and queued events
+    /// are kept, the new settings apply from the next event or sweep.
+    fn reload_config(&self) -> Result<Reloaded, String> {
+        let config = self.source.load()?;
+        let rules = load_all_rules().map_err(|e| e.to_string())?;
+
+        let restart_needed: Vec<String> = config
+            .needs_restart(&current(&self.config))
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        for setting in &restart_needed {
+            warn!(setting = setting; "⚠️  {} only changes on restart", setting);
+        }
+        log::init(config.log.level, config.log.format);
+        let reloaded = Reloaded {
+            rules: rules.len(),
+            restart_needed,
+        };
+        replace(&self.config, config);
+        replace(&self.rules, rules);
+        // So new rules apply right away.
+        self.sweep.store(true, Ordering::SeqCst);
+        Ok(reloaded)
+    }
+
+    /// Root and the user the daemon runs as can do anything. Anyone else
+    /// can only look at and change the expirations of their own files.
+    /// Returns the file a command is about, opened once so that what was
+    /// checked is what gets changed, even if its path is swapped meanwhile.
+    fn authorize(&self, peer: &Peer, command: &control::Command) -> Result<Option<File>, String> {
+        let path = match command {
+            control::Command::Show { path }
+            | control::Command::Cancel { path }
+            | control::Command::Extend { path, .. } => {
+                if !path.is_absolute() {
+                    return Err(format!("{} isn't absolute", escape(path)));
                 }
-            } else {
-                println!("╰ 😴 setattr's name should be `user.expire_at`");
+                path
             }
+            _ => return self.privileged(peer).map(|()| None),
+        };
+        let file = open_target(path)?;
+        if self.privileged(peer).is_ok() {
+            return Ok(file);
+        }
+        match file.as_ref().map(File::metadata) {
+            Some(Ok(metadata)) if metadata.uid() == peer.uid => Ok(file),
+            _ => Err(format!(
+                "permission denied: {} doesn't belong to uid {}",
+                escape(path),
+                peer.uid
+            )),
         }
     }
+
+    fn privileged(&self, peer: &Peer) -> Result<(), String> {
+        if peer.uid == 0 || peer.uid == self.uid {
+            Ok(())
+        } else {
+            Err(format!("permission denied for uid {}", peer.uid))
+        }
+    }
+
+    fn handle(&self, peer: &Peer, command: control::Command, file: Option<&File>) -> Response {
+        let store = lock_store(&self.store);
+        let store = store.as_ref();
+        match command {
+            control::Command::List {
+                due_before,
+                root,
+                limit,
+            } => {
+                let limit = limit.unwrap_or(control::LIST_LIMIT);
+                match store.pending(due_before, root.as_deref(), limit) {
+                    Ok(entries) => Response::ok(&entries),
+                    Err(e) => Response::error(e.to_string()),
+                }
+            }
+            control::Command::Show { path } => match shown(store, &path) {
+                Ok(shown) => Response::ok(&shown),
+                Err(e) => Response::error(e.to_string()),
+            },
+            control::Command::Cancel { path } => {
+                match cancel_file(store, &path, file, &current(&self.config).xattr.expire_at) {
+                    Ok(cancelled) => {
+                        info!(
+                            path = &path, pid = peer.pid, uid = peer.uid, outcome = "cancelled";
+                            "🚫 Cancelled {} expiration(s) of {} for pid {} (uid {})",
+                            cancelled,
+                            escape(&path),
+                            peer.pid,
+                            peer.uid
+                        );
+                        Response::ok(&Cancelled { cancelled })
+                    }
+                    Err(e) => Response::error(e),
+                }
+            }
+            control::Command::Extend { path, by } => {
+                match extend(store, &path, file, Duration::from_secs(by)) {
+                    Ok(expire_at) => {
+                        info!(
+                            path = &path, pid = peer.pid, uid = peer.uid, deadline = expire_at;
+                            "📅 Extended {} to {} for pid {} (uid {})",
+                            escape(&path),
+                            expire_at,
+                            peer.pid,
+                            peer.uid
+                        );
+                        Response::ok(&Extended { expire_at })
+                    }
+                    Err(e) => Response::error(e),
+                }
+            }
+            control::Command::Sweep => {
+                info!(
+                    pid = peer.pid, uid = peer.uid;
+                    "🧹 Sweep requested by pid {} (uid {})", peer.pid, peer.uid
+                );
+                self.sweep.store(true, Ordering::SeqCst);
+                Response::ok(&())
+            }
+            control::Command::Reload => {
+                info!(
+                    pid = peer.pid, uid = peer.uid;
+                    "🔄 Reload requested by pid {} (uid {})", peer.pid, peer.uid
+                );
+                match self.reload_config() {
+                    Ok(reloaded) => Response::ok(&reloaded),
+                    Err(e) => Response::error(e),
+                }
+            }
+            control::Command::Subscribe => unreachable!("subscriptions are streamed"),
+        }
+    }
+
+    /// Forwards notifications until the client hangs up, falls too far
+    /// behind, or the daemon stops.
+    fn stream(&self, writer: &mut &UnixStream) {
+        let events = self.hub.subscribe();
+        if send(writer, &Response::ok(&())).is_err() {
+            return;
+        }
+        while self.runnable.load(Ordering::SeqCst) {
+            match events.recv_timeout(Duration::from_millis(200)) {
+                Ok(event) => {
+                    if send(writer, &Response::event(event)).is_err() {
+                        return;
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => {
+                    let _ = send(writer, &Response::error("too far behind, subscribe again"));
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Checks the version before anything else, so a client speaking a newer
+/// protocol is told so instead of getting a confusing parse error.
+fn parse_request(line: &[u8]) -> Result<Request, String> {
+    #[derive(Deserialize)]
+    struct Versioned {
+        version: u32,
+    }
+    let Versioned { version } =
+        serde_json::from_slice(line).map_err(|e| format!("invalid request: {}", e))?;
+    if version != PROTOCOL_VERSION {
+        return Err(format!(
+            "unsupported protocol version {}, expected {}",
+            version, PROTOCOL_VERSION
+        ));
+    }
+    serde_json::from_slice(line).map_err(|e| format!("invalid request: {}", e))
+}
+
+fn send(writer: &mut impl Write, response: &Response) -> io::Result<()> {
+    serde_json::to_writer(&mut *writer, response)?;
+    writer.write_all(b"\n")
+}
+
+/// Options of the daemon, also taken without the `daemon` subcommand.
+#[derive(Args, Clone, Default)]
+struct DaemonArgs {
+    /// Log what would be done to expired files instead of doing it
+    #[arg(long)]
+    dry_run: bool,
+    /// Where the daemon keeps expirations, `memory` loses them on exit
+    /// [default: sqlite]
+    #[arg(long, value_enum)]
+    store: Option<Backend>,
+    /// Seconds given to finish pending work on SIGINT or SIGTERM before
+    /// exiting anyway [default: 30]
+    #[arg(long, value_name = "SECS")]
+    shutdown_timeout: Option<u64>,
+}
+
+impl DaemonArgs {
+    /// Takes the values set in `overrides` over ours.
+    fn merge(self, overrides: DaemonArgs) -> DaemonArgs {
+        DaemonArgs {
+            dry_run: self.dry_run || overrides.dry_run,
+            store: overrides.store.or(self.store),
+            shutdown_timeout: overrides.shutdown_timeout.or(self.shutdown_timeout),
+        }
+    }
+}
+
+#[derive(Parser)]
+#[command(about = "What if your filesystem supported file expiration?")]
+struct Cli {
+    /// Config file [default: /etc/sweeper/sweeper.toml]
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+    /// Database [default: /var/lib/sweeper/sweeper.db]
+    #[arg(long, global = true)]
+    db: Option<PathBuf>,
+    /// Lock file held by the daemon [default: <runtime-dir>/sweeper.lock]
+    #[arg(long, global = true)]
+    lock_file: Option<PathBuf>,
+    /// Control socket of the daemon [default: <runtime-dir>/sweeper.sock]
+    #[arg(long, global = true)]
+    socket: Option<PathBuf>,
+    /// Directory for the runtime state of the daemon [default: /run/sweeper]
+    #[arg(long, global = true)]
+    runtime_dir: Option<PathBuf>,
+    /// Print listings as JSON
+    #[arg(long, global = true)]
+    json: bool,
+    #[command(flatten)]
+    daemon: DaemonArgs,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the daemon, which is also what happens without a subcommand
+    Daemon(DaemonArgs),
+    /// List the pending expirations, earliest first
+    List {
+        /// Only those due before a timestamp, or an age from now such as `2h`
+        #[arg(long, value_name = "TIME")]
+        due_before: Option<String>,
+        /// Only the files under this directory
+        #[arg(long, value_name = "PATH")]
+        root: Option<PathBuf>,
+        /// How many to show at most
+        #[arg(long, default_value_t = control::LIST_LIMIT)]
+        limit: usize,
+    },
+    /// Show every expiration of a file and what happened to it
+    Show { path: PathBuf },
+    /// Make a file expire by setting its `user.expire_at` xattr, which the
+    /// running daemon picks up
+    Set {
+        path: PathBuf,
+        /// In how long, such as `30m`, `2h` or `1w2d`
+        #[arg(
+            long = "in",
+            value_name = "AGE",
+            required_unless_present = "at",
+            conflicts_with = "at"
+        )]
+        after: Option<String>,
+        /// When, as a timestamp
+        #[arg(long, value_name = "TIMESTAMP")]
+        at: Option<u64>,
+        /// Wait for the running daemon to register it
+        #[arg(long)]
+        confirm: bool,
+    },
+    /// Cancel the pending expirations of a file and remove its
+    /// `user.expire_at` xattr. Files matched by a rule are scheduled again
+    /// by the next scan.
+    Cancel { path: PathBuf },
+    /// Show the latest state changes, of every file
+    History {
+        /// How many to show
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+    },
+    /// Move a quarantined file back to its original location
+    Restore {
+        /// Quarantine id or original path of the file
+        target: OsString,
+    },
+    /// Show what dry-run mode would have done
+    Report,
+    /// List the files that couldn't be expired
+    Failed,
+    /// Try expiring a failed entry again
+    Retry {
+        /// Id of the entry, as shown by `failed`
+        id: i64,
+    },
 }
 
-fn delete(event: &Event) -> Result<()> {
-    // Show drift?
-    println!("🚮 Deleting {}", event.path);
+/// Makes a path given on the command line absolute, as stored by the
+/// daemon. Symlinks are left alone, they're expired themselves.
+fn absolute(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|e| {
+        eprintln!("Error: {}: {}", escape(path), e);
+        std::process::exit(1);
+    })
+}
+
+/// Parses a timestamp, or an age from now such as `2h`.
+fn parse_time(time: &str) -> Result<i64, String> {
+    if let Ok(timestamp) = time.parse::<i64>() {
+        return Ok(timestamp);
+    }
+    Ok(epoch(SystemTime::now() + parse_age(time)?))
+}
+
+fn print_json<T: Serialize + ?Sized>(value: &T) {
+    println!("{}", serde_json::to_string_pretty(value).unwrap());
+}
+
+fn list(
+    config: &Config,
+    json: bool,
+    due_before: Option<&str>,
+    root: Option<&Path>,
+    limit: usize,
+) -> Result<(), SweeperError> {
+    let due_before = due_before.map(|time| {
+        parse_time(time).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        })
+    });
+    let root = root.map(absolute);
+    let store = open_db(config);
 
-    // Check that the file indeed has the expire_at xattr
-    fs::remove_file(&event.path).unwrap();
+    let entries = store.pending(due_before, root.as_deref(), limit)?;
+    if json {
+        print_json(&entries);
+        return Ok(());
+    }
+    for entry in entries {
+        println!(
+            "{}\t{}\t{}\t{}",
+            entry.id.unwrap_or_default(),
+            entry.expire_at,
+            escape(&entry.path),
+            entry.name
+        );
+    }
     Ok(())
 }
 
-fn clean_up(conn: Arc<Mutex<Connection>>, runnable: Arc<AtomicBool>) {
-    let conn = conn.lock().unwrap();
+fn show(config: &Config, json: bool, path: &Path) -> Result<(), SweeperError> {
+    let path = absolute(path);
+    let store = open_db(config);
+
+    let shown = shown(&store, &path)?;
+    if json {
+        print_json(&shown);
+        return Ok(());
+    }
+    if shown.is_empty() {
+        eprintln!("Error: no expiration recorded for {}", escape(&path));
+        std::process::exit(1);
+    }
+    for Shown { entry, history } in shown {
+        println!(
+            "#{}\t{}\t{}\t{}",
+            entry.id.unwrap_or_default(),
+            entry.name,
+            entry.expire_at,
+            entry.state
+        );
+        for transition in history {
+            println!(
+                "\t{}\t{}\t{}",
+                transition.at,
+                transition.state,
+                transition.error.as_deref().unwrap_or("-")
+            );
+        }
+    }
+    Ok(())
+}
 
-    while runnable.load(Ordering::SeqCst) {
-        let mut stmt = conn
-            .prepare("SELECT * from sweeper where expire_at <= strftime('%s', 'now')")
-            .unwrap();
+fn set(config: &Config, path: &Path, after: Option<&str>, at: Option<u64>, confirm: bool) {
+    let path = absolute(path);
+    // The one the daemon watches, as it reads the same config.
+    let name = &config.xattr.expire_at;
+    let expire_at = match (at, after) {
+        (Some(at), _) => {
+            client::expire_at_with_name(&path, name, UNIX_EPOCH + Duration::from_secs(at))
+        }
+        (None, Some(after)) => parse_age(after)
+            .map_err(ClientError::Invalid)
+            .and_then(|age| client::expire_in_with_name(&path, name, age)),
+        (None, None) => unreachable!("clap requires --in or --at"),
+    }
+    .unwrap_or_else(|e| {
+        eprintln!("Error: {}: {}", escape(&path), e);
+        std::process::exit(1);
+    });
+    println!("📅 {} expires at {}", escape(&path), expire_at);
 
-        let sweep_iter = stmt
-            .query_map(params![], |row| {
-                Ok(Event {
-                    id: Some(row.get(0).unwrap()),
-                    path: row.get(1).unwrap(),
-                    name: row.get(2).unwrap(),
-                    expire_at: row.get(3).unwrap(),
-                })
-            })
-            .unwrap();
+    if confirm {
+        let registered = Client::connect(&config.socket).and_then(|mut client| {
+            client.confirm_with_name(&path, name, expire_at, CONFIRM_TIMEOUT)
+        });
+        match registered {
+            Ok(entry) => println!("╰ ✅ Registered as #{}", entry.id.unwrap_or_default()),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+fn cancel(config: &Config, path: &Path) {
+    let path = absolute(path);
+    let store = open_db(config);
+
+    let cancelled = open_target(&path)
+        .and_then(|file| cancel_file(&store, &path, file.as_ref(), &config.xattr.expire_at))
+        .unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        });
+    println!(
+        "🚫 Cancelled {} expiration(s) of {}",
+        cancelled,
+        escape(&path)
+    );
+}
+
+/// A state change along with the file it's about, for `history`.
+#[derive(Serialize)]
+struct Change {
+    #[serde(flatten)]
+    transition: Transition,
+    #[serde(serialize_with = "escape::serialize")]
+    path: PathBuf,
+}
+
+fn history(config: &Config, json: bool, limit: usize) -> Result<(), SweeperError> {
+    let store = open_db(config);
+
+    let mut paths: HashMap<i64, PathBuf> = HashMap::new();
+    let mut changes = Vec::new();
+    for transition in store.recent_transitions(limit)? {
+        let path = match paths.get(&transition.entry_id) {
+            Some(path) => path.clone(),
+            None => {
+                let path = store
+                    .entry(transition.entry_id)?
+                    .map(|entry| entry.path)
+                    .unwrap_or_default();
+                paths.insert(transition.entry_id, path.clone());
+                path
+            }
+        };
+        changes.push(Change { transition, path });
+    }
+
+    if json {
+        print_json(&changes);
+        return Ok(());
+    }
+    for Change { transition, path } in changes {
+        println!(
+            "{}\t#{}\t{}\t{}\t{}",
+            transition.at,
+            transition.entry_id,
+            transition.state,
+            escape(&path),
+            transition.error.as_deref().unwrap_or("-")
+        );
+    }
+    Ok(())
+}
 
-        for sweep in sweep_iter {
-            let thing = sweep.unwrap();
-            // Make atomic
-            // Maybe mark as deleted
-            conn.execute("DELETE FROM sweeper WHERE id = ?1", params![&thing.id])
-                .unwrap();
-            delete(&thing).unwrap();
+fn restore(config: &Config, target: &OsStr) {
+    let store = open_db(config);
+    // Paths are looked up as stored, ids as given.
+    let target = match target.to_str().and_then(|id| id.parse::<i64>().ok()) {
+        Some(_) => target.to_os_string(),
+        None => absolute(Path::new(target)).into_os_string(),
+    };
+
+    match quarantine::restore(&store, &target) {
+        Ok(path) => println!("♻️  Restored {}", escape(&path)),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
         }
+    }
+}
+
+fn report(config: &Config, json: bool) -> Result<(), SweeperError> {
+    let store = open_db(config);
+
+    let dry_runs = store.dry_runs()?;
+    if json {
+        print_json(&dry_runs);
+        return Ok(());
+    }
+    let mut total = 0;
+    for dry_run in dry_runs {
+        let size = dry_run.size.map_or("-".to_string(), |size| {
+            total += size;
+            size.to_string()
+        });
+        println!(
+            "{}\t{}\t{}\t{}\t{}",
+            dry_run.last_seen,
+            dry_run.action,
+            size,
+            escape(&dry_run.path),
+            dry_run.reason
+        );
+    }
+    println!("Total: {total} bytes");
+    Ok(())
+}
+
+fn failed(config: &Config, json: bool) -> Result<(), SweeperError> {
+    let store = open_db(config);
+
+    let entries = store.entries(State::Failed)?;
+    if json {
+        print_json(&entries);
+        return Ok(());
+    }
+    for entry in entries {
+        println!(
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            entry.id.unwrap_or_default(),
+            entry.updated_at,
+            entry.action.as_deref().unwrap_or("-"),
+            entry.attempts,
+            escape(&entry.path),
+            entry.error.as_deref().unwrap_or("-")
+        );
+    }
+    Ok(())
+}
+
+fn retry_failed(config: &Config, id: i64) -> Result<(), SweeperError> {
+    let store = open_db(config);
+
+    let retried = store.transition(
+        id,
+        State::Failed,
+        State::Pending,
+        Update {
+            attempts: Some(0),
+            ..Update::default()
+        },
+    )?;
+    if retried {
+        println!("🔁 Entry #{} will be retried", id);
+    } else {
+        eprintln!("Error: no failed entry #{}", id);
+        std::process::exit(1);
+    }
+    Ok(())
+}
 
-        thread::sleep(time::Duration::from_millis(100));
+/// Ends the process with the error of a subcommand, if it failed.
+fn exit_on_error(result: Result<(), SweeperError>) {
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
     }
 }
 
 fn main() {
-    println!("🧹🧹🧹🧹 Sweeper 🧹🧹🧹🧹");
+    let cli = Cli::parse();
+    let daemon_args = match &cli.command {
+        Some(Command::Daemon(args)) => cli.daemon.clone().merge(args.clone()),
+        _ => cli.daemon.clone(),
+    };
 
-    let conn = Connection::open("test.db").unwrap();
-    let cleaner_conn = Connection::open("test.db").unwrap();
+    let config_path = cli
+        .config
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(CONFIG_PATH));
+    // Kept by the daemon to load the config again on reload, the flags
+    // still winning over the file.
+    let source = Source {
+        path: config_path,
+        required: cli.config.is_some(),
+        overrides: Settings {
+            db_path: cli.db,
+            lock_file: cli.lock_file,
+            socket: cli.socket,
+            runtime_dir: cli.runtime_dir,
+            store: daemon_args.store,
+            shutdown_timeout: daemon_args.shutdown_timeout,
+            ..Default::default()
+        },
+    };
+    let config = source.load().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
 
-    // We need a Atomic Reference Count because ctrlc spawns a thread for
-    // signal delivering
-    let runnable = Arc::new(AtomicBool::new(true));
-    let r = runnable.clone();
-    ctrlc::set_handler(move || {
-        r.store(false, Ordering::SeqCst);
-    })
-    .expect("ctrlc");
+    match cli.command {
+        None | Some(Command::Daemon(_)) => daemon(config, source, daemon_args.dry_run),
+        Some(Command::List {
+            due_before,
+            root,
+            limit,
+        }) => exit_on_error(list(
+            &config,
+            cli.json,
+            due_before.as_deref(),
+            root.as_deref(),
+            limit,
+        )),
+        Some(Command::Show { path }) => exit_on_error(show(&config, cli.json, &path)),
+        Some(Command::Set {
+            path,
+            after,
+            at,
+            confirm,
+        }) => set(&config, &path, after.as_deref(), at, confirm),
+        Some(Command::Cancel { path }) => cancel(&config, &path),
+        Some(Command::History { limit }) => exit_on_error(history(&config, cli.json, limit)),
+        Some(Command::Restore { target }) => restore(&config, &target),
+        Some(Command::Report) => exit_on_error(report(&config, cli.json)),
+        Some(Command::Failed) => exit_on_error(failed(&config, cli.json)),
+        Some(Command::Retry { id }) => exit_on_error(retry_failed(&config, id)),
+    }
+}
+
+fn daemon(config: Config, source: Source, dry_run: bool) {
+    log::init(config.log.level, config.log.format);
+    info!("🧹🧹🧹🧹 Sweeper 🧹🧹🧹🧹");
+    if dry_run {
+        info!("🧪 Dry-run mode, nothing will be touched");
+    }
+
+    if let Err(e) = fs::create_dir_all(&config.runtime_dir) {
+        eprintln!("Error: {}: {}", escape(&config.runtime_dir), e);
+        std::process::exit(1);
+    }
+    // Held until we exit, so a second instance can't work on the same store.
+    let _lock = lock::acquire(&config.lock_file).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
+    info!(path = &config.db_path; "🗄  Using {}", escape(&config.db_path));
+
+    let rules = load_all_rules().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
+
+    // Signals are delivered to a thread of their own, which only raises
+    // flags: the reload itself happens on the control thread. A second
+    // SIGINT or SIGTERM exits right away.
+    let signals = Signals {
+        runnable: Arc::new(AtomicBool::new(true)),
+        reload: Arc::new(AtomicBool::new(false)),
+    };
+    let mut incoming = signal_hook::iterator::Signals::new([SIGINT, SIGTERM, SIGHUP])
+        .unwrap_or_else(|e| {
+            eprintln!("Error: signals: {}", e);
+            std::process::exit(1);
+        });
+    let (runnable, reload) = (signals.runnable.clone(), signals.reload.clone());
+    thread::spawn(move || {
+        for signal in incoming.forever() {
+            if signal == SIGHUP {
+                reload.store(true, Ordering::SeqCst);
+            } else if !runnable.swap(false, Ordering::SeqCst) {
+                eprintln!("Error: interrupted again, exiting now");
+                std::process::exit(1);
+            }
+        }
+    });
 
-    let sweeper = Sweeper::new(conn, cleaner_conn, runnable);
+    let sweeper = Sweeper::new(config, source, &signals, rules, dry_run);
     if let Err(e) = sweeper.run() {
-        eprintln!("Error: {:?}", e);
+        eprintln!("Error: {}", e);
         std::process::exit(1);
     }
 }
@@ -0,0 +1,68 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Counters of the ingestion pipeline, from the perf buffer to the store.
+/// Shared between the BPF callbacks and the ingestion thread.
+#[derive(Debug, Default)]
+pub struct IngestMetrics {
+    /// Events waiting in the channel.
+    depth: AtomicU64,
+    max_depth: AtomicU64,
+    /// Events written to the store, and in how many transactions.
+    ingested: AtomicU64,
+    batches: AtomicU64,
+    /// Events the kernel dropped because the perf buffer was full.
+    lost: AtomicU64,
+    /// Events that couldn't be decoded.
+    invalid: AtomicU64,
+    /// Events decoded but not written, as the store failed.
+    failed: AtomicU64,
+}
+
+impl IngestMetrics {
+    pub fn queued(&self) {
+        let depth = self.depth.fetch_add(1, Ordering::Relaxed) + 1;
+        self.max_depth.fetch_max(depth, Ordering::Relaxed);
+    }
+
+    /// A batch of `count` events left the channel and was written.
+    pub fn ingested(&self, count: u64) {
+        self.depth.fetch_sub(count, Ordering::Relaxed);
+        self.ingested.fetch_add(count, Ordering::Relaxed);
+        self.batches.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn lost(&self, count: u64) {
+        self.lost.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn invalid(&self) {
+        self.invalid.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A batch of `count` events left the channel but couldn't be written.
+    pub fn failed(&self, count: u64) {
+        self.depth.fetch_sub(count, Ordering::Relaxed);
+        self.failed.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn depth(&self) -> u64 {
+        self.depth.load(Ordering::Relaxed)
+    }
+}
+
+impl fmt::Display for IngestMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} event(s) ingested in {} batch(es), {} queued (max {}), {} lost, {} invalid, {} failed",
+            self.ingested.load(Ordering::Relaxed),
+            self.batches.load(Ordering::Relaxed),
+            self.depth.load(Ordering::Relaxed),
+            self.max_depth.load(Ordering::Relaxed),
+            self.lost.load(Ordering::Relaxed),
+            self.invalid.load(Ordering::Relaxed),
+            self.failed.load(Ordering::Relaxed),
+        )
+    }
+}
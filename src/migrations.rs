@@ -0,0 +1,299 @@
+use rusqlite::Connection;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Every schema change, in order. Migration `i` upgrades a database at
+/// `PRAGMA user_version` `i` to `i + 1`. Released migrations must never be
+/// edited, only new ones appended.
+const MIGRATIONS: &[&str] = &[
+    // 1: the original table, only holding pending expirations.
+    "CREATE TABLE IF NOT EXISTS sweeper (
+        id INTEGER PRIMARY KEY,
+        path TEXT NOT NULL,
+        name TEXT NOT NULL,
+        expire_at timestamp NOT NULL);",
+    // 2: entries are kept with their state, rules, actions, dry-run and
+    // quarantine. Rows from older versions were all still pending.
+    "ALTER TABLE sweeper ADD COLUMN dev INTEGER;
+    ALTER TABLE sweeper ADD COLUMN ino INTEGER;
+    ALTER TABLE sweeper ADD COLUMN state TEXT NOT NULL DEFAULT 'pending';
+    ALTER TABLE sweeper ADD COLUMN pid INTEGER;
+    ALTER TABLE sweeper ADD COLUMN uid INTEGER;
+    ALTER TABLE sweeper ADD COLUMN comm TEXT;
+    ALTER TABLE sweeper ADD COLUMN action TEXT;
+    ALTER TABLE sweeper ADD COLUMN bytes_freed INTEGER;
+    ALTER TABLE sweeper ADD COLUMN error TEXT;
+    ALTER TABLE sweeper ADD COLUMN attempts INTEGER NOT NULL DEFAULT 0;
+    ALTER TABLE sweeper ADD COLUMN next_attempt_at timestamp;
+    ALTER TABLE sweeper ADD COLUMN created_at timestamp NOT NULL DEFAULT 0;
+    ALTER TABLE sweeper ADD COLUMN updated_at timestamp NOT NULL DEFAULT 0;
+    UPDATE sweeper SET created_at = strftime('%s', 'now'), updated_at = strftime('%s', 'now');
+    CREATE TABLE transitions (
+        id INTEGER PRIMARY KEY,
+        entry_id INTEGER NOT NULL,
+        state TEXT NOT NULL,
+        at timestamp NOT NULL,
+        error TEXT);
+    INSERT INTO transitions (entry_id, state, at) SELECT id, state, created_at FROM sweeper;
+    CREATE TABLE dry_run_history (
+        id INTEGER PRIMARY KEY,
+        path TEXT NOT NULL,
+        name TEXT NOT NULL,
+        action TEXT NOT NULL,
+        size INTEGER,
+        reason TEXT NOT NULL,
+        first_seen timestamp NOT NULL,
+        last_seen timestamp NOT NULL,
+        UNIQUE (path, name, action));
+    CREATE TABLE quarantine (
+        id INTEGER PRIMARY KEY,
+        path TEXT NOT NULL,
+        trash_path TEXT NOT NULL,
+        uid INTEGER NOT NULL,
+        gid INTEGER NOT NULL,
+        mode INTEGER NOT NULL,
+        quarantined_at timestamp NOT NULL,
+        purge_at timestamp NOT NULL,
+        restored_at timestamp,
+        purged_at timestamp);",
+    // 3: indexes for the cleaner's due entries, the lookups by file when
+    // scheduling, and the audit trail.
+    "CREATE INDEX sweeper_deadline ON sweeper (state, expire_at);
+    CREATE INDEX sweeper_file ON sweeper (path, name);
+    CREATE INDEX sweeper_inode ON sweeper (dev, ino);
+    CREATE INDEX transitions_entry ON transitions (entry_id);
+    CREATE INDEX quarantine_purge ON quarantine (purge_at) WHERE restored_at IS NULL AND purged_at IS NULL;",
+    // 4: paths are bytes, stored as BLOBs so the ones that aren't UTF-8
+    // survive. A BLOB never equals a TEXT, so existing paths are converted.
+    "UPDATE sweeper SET path = CAST(path AS BLOB) WHERE typeof(path) = 'text';
+    UPDATE dry_run_history SET path = CAST(path AS BLOB) WHERE typeof(path) = 'text';
+    UPDATE quarantine SET path = CAST(path AS BLOB), trash_path = CAST(trash_path AS BLOB)
+        WHERE typeof(path) = 'text' OR typeof(trash_path) = 'text';",
+];
+
+/// Schema version this binary works with.
+pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+fn user_version(conn: &Connection) -> rusqlite::Result<u32> {
+    conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+}
+
+/// Copies the database next to itself before it's migrated, as
+/// `<db>.v<version>-<epoch>.bak`. In-memory databases aren't backed up.
+fn backup(conn: &Connection, version: u32) -> Result<Option<PathBuf>, String> {
+    let path = match conn.path() {
+        Some(path) if !path.is_empty() => path,
+        _ => return Ok(None),
+    };
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let destination = PathBuf::from(format!("{path}.v{version}-{now}.bak"));
+
+    conn.execute("VACUUM INTO ?1", [destination.to_string_lossy()])
+        .map_err(|e| format!("backing up to {}: {e}", destination.display()))?;
+    Ok(Some(destination))
+}
+
+/// Brings the schema of the database up to `SCHEMA_VERSION`, taking a
+/// backup first if it already held data. Each migration runs in its own
+/// transaction along with the version bump, so an interrupted upgrade
+/// resumes where it stopped. Refuses databases from a newer version.
+pub fn migrate(conn: &Connection) -> Result<(), String> {
+    let version = user_version(conn).map_err(|e| e.to_string())?;
+    if version > SCHEMA_VERSION {
+        return Err(format!(
+            "the database is at schema version {version}, newer than the {SCHEMA_VERSION} this version of sweeper supports, please upgrade sweeper"
+        ));
+    }
+    if version == SCHEMA_VERSION {
+        return Ok(());
+    }
+
+    let tables: u32 = conn
+        .query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+    if tables > 0 {
+        if let Some(backup) = backup(conn, version)? {
+            crate::info!(
+                "💾 Backed up the database to {} before migrating it from version {} to {}",
+                backup.display(),
+                version,
+                SCHEMA_VERSION
+            );
+        }
+    }
+
+    for (i, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+        tx.execute_batch(migration)
+            .and_then(|_| tx.pragma_update(None, "user_version", i as u32 + 1))
+            .and_then(|_| tx.commit())
+            .map_err(|e| format!("migration to version {}: {e}", i + 1))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    /// A database as created by the first release, which didn't set its
+    /// `user_version`, with a couple of pending expirations.
+    fn v1(path: &Path) -> Connection {
+        let conn = Connection::open(path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS sweeper (
+                id INTEGER PRIMARY KEY,
+                path TEXT NOT NULL,
+                name TEXT NOT NULL,
+                expire_at timestamp NOT NULL);
+            INSERT INTO sweeper (path, name, expire_at)
+                VALUES ('/tmp/a', 'user.expire_at', 100), ('/tmp/b', 'user.expire_at', 200);",
+        )
+        .unwrap();
+        conn
+    }
+
+    fn backups(dir: &Path) -> Vec<PathBuf> {
+        fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.to_string_lossy().ends_with(".bak"))
+            .collect()
+    }
+
+    #[test]
+    fn migrates_a_v1_database() {
+        let tmp = TempDir::new().unwrap();
+        let conn = v1(&tmp.path().join("sweeper.db"));
+
+        migrate(&conn).unwrap();
+
+        assert_eq!(user_version(&conn).unwrap(), SCHEMA_VERSION);
+        let rows: Vec<(Vec<u8>, String, i64, String, String)> = conn
+            .prepare("SELECT path, typeof(path), expire_at, state, name FROM sweeper ORDER BY id")
+            .unwrap()
+            .query_map([], |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                ))
+            })
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap();
+        assert_eq!(
+            rows,
+            [
+                (
+                    b"/tmp/a".to_vec(),
+                    "blob".to_string(),
+                    100,
+                    "pending".to_string(),
+                    "user.expire_at".to_string()
+                ),
+                (
+                    b"/tmp/b".to_vec(),
+                    "blob".to_string(),
+                    200,
+                    "pending".to_string(),
+                    "user.expire_at".to_string()
+                ),
+            ]
+        );
+        let transitions: u32 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM transitions WHERE state = 'pending'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(transitions, 2);
+        // Already up to date.
+        migrate(&conn).unwrap();
+    }
+
+    #[test]
+    fn backs_up_before_migrating() {
+        let tmp = TempDir::new().unwrap();
+        let conn = v1(&tmp.path().join("sweeper.db"));
+
+        migrate(&conn).unwrap();
+
+        let backups = backups(tmp.path());
+        assert_eq!(backups.len(), 1);
+        assert!(backups[0].to_string_lossy().contains("sweeper.db.v0-"));
+        let backup = Connection::open(&backups[0]).unwrap();
+        assert_eq!(user_version(&backup).unwrap(), 0);
+        let rows: u32 = backup
+            .query_row("SELECT COUNT(*) FROM sweeper", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(rows, 2);
+    }
+
+    #[test]
+    fn does_not_back_up_new_databases() {
+        let tmp = TempDir::new().unwrap();
+        let conn = Connection::open(tmp.path().join("sweeper.db")).unwrap();
+
+        migrate(&conn).unwrap();
+
+        assert_eq!(user_version(&conn).unwrap(), SCHEMA_VERSION);
+        assert!(backups(tmp.path()).is_empty());
+    }
+
+    #[test]
+    fn refuses_newer_databases() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION + 1)
+            .unwrap();
+
+        assert!(migrate(&conn).is_err());
+        assert_eq!(user_version(&conn).unwrap(), SCHEMA_VERSION + 1);
+    }
+
+    #[test]
+    fn converts_text_paths_to_blobs() {
+        let conn = Connection::open_in_memory().unwrap();
+        for migration in &MIGRATIONS[..3] {
+            conn.execute_batch(migration).unwrap();
+        }
+        conn.pragma_update(None, "user_version", 3).unwrap();
+        conn.execute_batch(
+            "INSERT INTO sweeper (path, name, expire_at) VALUES ('/tmp/a', 'user.expire_at', 1);
+            INSERT INTO dry_run_history (path, name, action, reason, first_seen, last_seen)
+                VALUES ('/tmp/b', 'user.expire_at', 'delete', 'due', 1, 1);
+            INSERT INTO quarantine (path, trash_path, uid, gid, mode, quarantined_at, purge_at)
+                VALUES ('/tmp/c', '/.sweeper-trash/1-c', 0, 0, 420, 1, 2);",
+        )
+        .unwrap();
+
+        migrate(&conn).unwrap();
+
+        for (column, table, expected) in [
+            ("path", "sweeper", "/tmp/a"),
+            ("path", "dry_run_history", "/tmp/b"),
+            ("path", "quarantine", "/tmp/c"),
+            ("trash_path", "quarantine", "/.sweeper-trash/1-c"),
+        ] {
+            let (path, kind): (Vec<u8>, String) = conn
+                .query_row(
+                    &format!("SELECT {column}, typeof({column}) FROM {table}"),
+                    [],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .unwrap();
+            assert_eq!(path, expected.as_bytes(), "{table}.{column}");
+            assert_eq!(kind, "blob", "{table}.{column}");
+        }
+    }
+}
@@ -0,0 +1,280 @@
+use crate::action::{Action, Outcome};
+use crate::escape::escape;
+use crate::store::{self, Quarantined, Store};
+use crate::target::Target;
+use std::ffi::OsStr;
+use std::fs::{self, DirBuilder};
+use std::io;
+use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Name of the quarantine directory created at the root of every mount.
+pub const TRASH_DIR: &str = ".sweeper-trash";
+pub const DEFAULT_GRACE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Moves the file into the `.sweeper-trash` directory of its mount. As it's
+/// a rename within the same filesystem, ownership, permissions and xattrs
+/// are kept. The file is purged once `grace` has passed, unless restored.
+pub struct Quarantine {
+    pub grace: Duration,
+}
+
+impl Action for Quarantine {
+    fn spec(&self) -> String {
+        format!("quarantine:{}s", self.grace.as_secs())
+    }
+
+    fn apply(&self, target: &Target) -> io::Result<Outcome> {
+        let destination = self.trash_path(target)?;
+        self.apply_to(target, &destination)
+    }
+
+    fn destination(&self, target: &Target) -> io::Result<Option<(PathBuf, Duration)>> {
+        Ok(Some((self.trash_path(target)?, self.grace)))
+    }
+
+    fn apply_to(&self, target: &Target, destination: &Path) -> io::Result<Outcome> {
+        target.rename_to(destination)?;
+
+        Ok(Outcome {
+            moved_to: Some(destination.to_path_buf()),
+            purge_after: Some(self.grace),
+            ..Outcome::default()
+        })
+    }
+}
+
+impl Quarantine {
+    /// Picks where in the trash of its mount `target` goes, creating the
+    /// trash if needed.
+    fn trash_path(&self, target: &Target) -> io::Result<PathBuf> {
+        let path = target.path();
+        let metadata = target.metadata()?;
+        let trash = mount_point(path, metadata.dev())?.join(TRASH_DIR);
+        DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(&trash)?;
+        check_trash(&trash)?;
+
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let mut name = std::ffi::OsString::from(format!("{nanos}-"));
+        name.push(path.file_name().unwrap_or_default());
+        Ok(trash.join(name))
+    }
+}
+
+/// Makes sure `trash` is the directory we'd have created, not one, or a
+/// symlink, someone else put there beforehand to get files moved their way.
+fn check_trash(trash: &Path) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(trash)?;
+    if !metadata.is_dir() || metadata.uid() != 0 || metadata.mode() & 0o7777 != 0o700 {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!(
+                "{} must be a directory owned by root with mode 0700",
+                escape(trash)
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the topmost ancestor of `path` that is still on device `dev`.
+fn mount_point(path: &Path, dev: u64) -> io::Result<PathBuf> {
+    let mut mount = PathBuf::from("/");
+    for ancestor in path.ancestors().skip(1) {
+        if fs::metadata(ancestor)?.dev() != dev {
+            break;
+        }
+        mount = ancestor.to_path_buf();
+    }
+    Ok(mount)
+}
+
+/// Whether `path` lives inside a quarantine directory.
+pub fn is_quarantined(path: &Path) -> bool {
+    path.components().any(|c| c.as_os_str() == TRASH_DIR)
+}
+
+/// Remembers where a file is about to be quarantined from so it can be
+/// restored. Done before moving it, so the move can't go unrecorded.
+pub fn record(
+    store: &dyn Store,
+    target: &Target,
+    trash_path: &Path,
+    grace: Duration,
+) -> store::Result<i64> {
+    let (uid, gid, mode) = match target.metadata() {
+        Ok(metadata) => (metadata.uid(), metadata.gid(), metadata.mode()),
+        Err(_) => (0, 0, 0),
+    };
+    let now = store::now();
+    store.quarantine(&Quarantined {
+        id: None,
+        path: target.path().to_path_buf(),
+        trash_path: trash_path.to_path_buf(),
+        uid,
+        gid,
+        mode,
+        quarantined_at: now,
+        purge_at: now.saturating_add(grace.as_secs() as i64),
+    })
+}
+
+/// Permanently removes every quarantined file whose grace period is over.
+pub fn purge(store: &dyn Store) -> store::Result<Vec<PathBuf>> {
+    let mut purged = Vec::new();
+    for quarantined in store.purgeable()? {
+        let trash_path = &quarantined.trash_path;
+        match fs::remove_file(trash_path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => {
+                crate::error!(
+                    path = trash_path;
+                    "Error purging {}: {}", escape(trash_path), e
+                );
+                continue;
+            }
+        }
+        if let Some(id) = quarantined.id {
+            store.mark_purged(id)?;
+        }
+        purged.push(quarantined.trash_path);
+    }
+
+    Ok(purged)
+}
+
+/// Moves a quarantined file back to where it was. `target` is either the id
+/// of the quarantine entry or the original path, in which case the most
+/// recently quarantined file for that path is restored.
+pub fn restore(store: &dyn Store, target: &OsStr) -> Result<PathBuf, String> {
+    let quarantined = match target.to_str().and_then(|id| id.parse::<i64>().ok()) {
+        Some(id) => store.quarantined(id),
+        None => store.last_quarantined(Path::new(target)),
+    }
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("nothing quarantined matches `{}`", escape(target)))?;
+
+    let path = quarantined.path;
+    if fs::symlink_metadata(&path).is_ok() {
+        return Err(format!("{} already exists", escape(&path)));
+    }
+    let trash_path = &quarantined.trash_path;
+    fs::rename(trash_path, &path).map_err(|e| format!("{}: {e}", escape(trash_path)))?;
+
+    if let Some(id) = quarantined.id {
+        store.mark_restored(id).map_err(|e| e.to_string())?;
+    }
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::MemoryStore;
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::TempDir;
+
+    /// Puts a file in `dir/trash` as if it had been quarantined from
+    /// `dir/name`, to be purged after `grace`.
+    fn quarantined(store: &dyn Store, dir: &Path, name: &str, grace: i64) -> i64 {
+        fs::create_dir_all(dir.join("trash")).unwrap();
+        let trash_path = dir.join("trash").join(name);
+        fs::write(&trash_path, name).unwrap();
+        let now = store::now();
+        store
+            .quarantine(&Quarantined {
+                id: None,
+                path: dir.join(name),
+                trash_path,
+                uid: 0,
+                gid: 0,
+                mode: 0o100644,
+                quarantined_at: now,
+                purge_at: now + grace,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn restores_by_id_or_path() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let store = MemoryStore::new();
+        let id = quarantined(&store, dir, "by-id", 60);
+        quarantined(&store, dir, "by-path", 60);
+
+        let restored = restore(&store, OsStr::new(&id.to_string())).unwrap();
+        assert_eq!(restored, dir.join("by-id"));
+        let restored = restore(&store, dir.join("by-path").as_os_str()).unwrap();
+        assert_eq!(restored, dir.join("by-path"));
+
+        assert_eq!(fs::read(dir.join("by-id")).unwrap(), b"by-id");
+        assert_eq!(fs::read(dir.join("by-path")).unwrap(), b"by-path");
+        assert!(!dir.join("trash/by-id").exists());
+        assert!(store.quarantined(id).unwrap().is_none());
+    }
+
+    #[test]
+    fn restores_over_nothing() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let store = MemoryStore::new();
+        let id = quarantined(&store, dir, "taken", 60);
+        fs::write(dir.join("taken"), b"new").unwrap();
+
+        assert!(restore(&store, OsStr::new(&id.to_string())).is_err());
+        assert!(restore(&store, OsStr::new("12345")).is_err());
+
+        assert_eq!(fs::read(dir.join("taken")).unwrap(), b"new");
+        assert!(dir.join("trash/taken").exists());
+    }
+
+    #[test]
+    fn purges_once_the_grace_period_is_over() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let store = MemoryStore::new();
+        let over = quarantined(&store, dir, "over", -1);
+        let kept = quarantined(&store, dir, "kept", 60);
+
+        let purged = purge(&store).unwrap();
+
+        assert_eq!(purged, [dir.join("trash/over")]);
+        assert!(!dir.join("trash/over").exists());
+        assert!(dir.join("trash/kept").exists());
+        assert!(store.quarantined(over).unwrap().is_none());
+        assert!(store.quarantined(kept).unwrap().is_some());
+    }
+
+    #[test]
+    fn refuses_trashes_it_did_not_create() {
+        // Only root gets a trash it accepts, and can give it away.
+        if unsafe { libc::geteuid() } != 0 {
+            return;
+        }
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let trash = dir.join(TRASH_DIR);
+        DirBuilder::new().mode(0o700).create(&trash).unwrap();
+        check_trash(&trash).unwrap();
+
+        fs::set_permissions(&trash, fs::Permissions::from_mode(0o777)).unwrap();
+        assert!(check_trash(&trash).is_err());
+        std::os::unix::fs::chown(&trash, Some(1000), None).unwrap();
+        fs::set_permissions(&trash, fs::Permissions::from_mode(0o700)).unwrap();
+        assert!(check_trash(&trash).is_err());
+
+        let link = dir.join("link");
+        std::os::unix::fs::symlink(dir, &link).unwrap();
+        assert!(check_trash(&link).is_err());
+    }
+}
@@ -0,0 +1,54 @@
+use crate::target::Target;
+use std::io;
+use std::time::Duration;
+
+/// Attempts made at expiring a file before giving up on it.
+pub const MAX_ATTEMPTS: u32 = 6;
+const BASE_DELAY: Duration = Duration::from_secs(30);
+const MAX_DELAY: Duration = Duration::from_secs(60 * 60);
+
+/// How an action failing with a given error should be handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Class {
+    /// Likely to go away by itself, e.g. a busy file or a filesystem being
+    /// remounted, or a permission that might be given back.
+    Transient,
+    /// The file disappeared before we got to it, there's nothing left to do.
+    Vanished,
+    /// Retrying won't help.
+    Permanent,
+}
+
+/// Classifies the error an action on `path` failed with.
+pub fn classify(err: &io::Error, target: &Target) -> Class {
+    match err.raw_os_error() {
+        Some(
+            libc::EBUSY
+            | libc::EROFS
+            | libc::EAGAIN
+            | libc::EINTR
+            | libc::ETXTBSY
+            | libc::EIO
+            | libc::ENOSPC
+            | libc::EDQUOT
+            | libc::EACCES
+            | libc::EPERM,
+        ) => Class::Transient,
+        // Only the file itself missing means it vanished, not e.g. the
+        // directory of an archive.
+        Some(libc::ENOENT) if target.metadata().is_err() => Class::Vanished,
+        _ => match err.kind() {
+            io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => {
+                Class::Transient
+            }
+            _ => Class::Permanent,
+        },
+    }
+}
+
+/// Delay before the next attempt, after `attempts` failed ones: doubles
+/// every time, from 30s up to an hour.
+pub fn backoff(attempts: u32) -> Duration {
+    let factor = 1u32 << attempts.saturating_sub(1).min(16);
+    BASE_DELAY.saturating_mul(factor).min(MAX_DELAY)
+}
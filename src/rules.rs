@@ -0,0 +1,511 @@
+use crate::action::{parse_action, DEFAULT_ACTION};
+use crate::quarantine::{is_quarantined, TRASH_DIR};
+use crate::target::{open_at, open_dir};
+use glob::{MatchOptions, Pattern};
+use serde::Deserialize;
+use std::ffi::{CStr, CString, OsStr};
+use std::fs::{self, File};
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::MetadataExt;
+use std::os::unix::io::IntoRawFd;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const RULES_DIR: &str = "/etc/sweeper/rules.d";
+
+/// As in a shell, `*` and `?` don't match a `/`, so a pattern reaches no
+/// deeper than it says. `**` matches any number of directories.
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// Which timestamp of a file is used to compute its age.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AgeBasis {
+    #[default]
+    Mtime,
+    Atime,
+    Ctime,
+    Btime,
+}
+
+/// Accepts either a single value or a list in the config file.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T> OneOrMany<T> {
+    fn into_vec(self) -> Vec<T> {
+        match self {
+            OneOrMany::One(value) => vec![value],
+            OneOrMany::Many(values) => values,
+        }
+    }
+}
+
+impl<T: Default> Default for OneOrMany<T> {
+    fn default() -> Self {
+        OneOrMany::One(T::default())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawRule {
+    name: Option<String>,
+    path: Option<String>,
+    glob: Option<String>,
+    #[serde(default)]
+    age: OneOrMany<AgeBasis>,
+    max_age: String,
+    #[serde(default = "default_action")]
+    action: String,
+    #[serde(default)]
+    exclude: Vec<String>,
+    #[serde(default)]
+    dry_run: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RulesFile {
+    #[serde(default)]
+    rule: Vec<RawRule>,
+}
+
+fn default_action() -> String {
+    DEFAULT_ACTION.to_string()
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum Matcher {
+    Prefix(PathBuf),
+    Glob(Pattern),
+}
+
+/// A path that is never touched. A recursive exclusion also covers
+/// everything below it.
+#[derive(Debug, Clone)]
+pub(crate) struct Exclusion {
+    pub(crate) pattern: Pattern,
+    pub(crate) recursive: bool,
+}
+
+/// A declarative cleanup rule, similar to a `tmpfiles.d` entry: every
+/// regular file under `root` that matches is due `max_age` after the most
+/// recent of its `ages` timestamps.
+#[derive(Debug, Clone)]
+pub struct Rule {
+    pub name: String,
+    pub ages: Vec<AgeBasis>,
+    pub max_age: Duration,
+    pub action: String,
+    /// Only report what would be done to the matching files.
+    pub dry_run: bool,
+    pub(crate) matcher: Matcher,
+    pub(crate) exclude: Vec<Exclusion>,
+}
+
+impl Rule {
+    /// Directory the rule has to walk to find candidates.
+    pub fn root(&self) -> PathBuf {
+        match &self.matcher {
+            Matcher::Prefix(prefix) => prefix.clone(),
+            Matcher::Glob(pattern) => {
+                let mut root = PathBuf::new();
+                for component in Path::new(pattern.as_str()).components() {
+                    let s = component.as_os_str().to_string_lossy();
+                    if s.contains(['*', '?', '[']) {
+                        break;
+                    }
+                    root.push(component);
+                }
+                root
+            }
+        }
+    }
+
+    pub(crate) fn matches(&self, path: &Path) -> bool {
+        match &self.matcher {
+            Matcher::Prefix(prefix) => path.starts_with(prefix),
+            Matcher::Glob(pattern) => pattern.matches_path_with(path, MATCH_OPTIONS),
+        }
+    }
+
+    fn excluded(&self, path: &Path) -> bool {
+        if is_quarantined(path) {
+            return true;
+        }
+        self.exclude.iter().any(|exclusion| {
+            exclusion.pattern.matches_path_with(path, MATCH_OPTIONS)
+                || (exclusion.recursive
+                    && path
+                        .ancestors()
+                        .any(|a| exclusion.pattern.matches_path_with(a, MATCH_OPTIONS)))
+        })
+    }
+
+    fn deadline_of(&self, metadata: &fs::Metadata) -> Option<SystemTime> {
+        self.ages
+            .iter()
+            .filter_map(|basis| timestamp(metadata, *basis))
+            .max()
+            .map(|newest| newest + self.max_age)
+    }
+
+    /// When the file at `path`, which `metadata` is from, is due according
+    /// to this rule, or `None` if the rule doesn't cover it (anymore).
+    pub fn deadline(&self, path: &Path, metadata: &fs::Metadata) -> Option<SystemTime> {
+        if !self.matches(path) || self.excluded(path) || !metadata.is_file() {
+            return None;
+        }
+        self.deadline_of(metadata)
+    }
+
+    /// Returns every file covered by this rule along with its deadline and
+    /// metadata. Walks from directory fds, never following a symlink, so a
+    /// directory swapped for one halfway through can't lead the walk
+    /// elsewhere. Nor does it reach a root behind a symlink.
+    pub fn scan(&self) -> Vec<(PathBuf, SystemTime, fs::Metadata)> {
+        let mut scheduled = Vec::new();
+        // Subdirectories are opened once popped, from their parent, so only
+        // the directories being walked are held open.
+        let mut pending = Vec::new();
+
+        let root = self.root();
+        if let Ok(dir) = open_dir(&root, libc::O_RDONLY) {
+            self.scan_dir(&Rc::new(dir), &root, &mut pending, &mut scheduled);
+        }
+        while let Some((parent, path, name)) = pending.pop() {
+            if let Ok(dir) = open_at(&parent, &name, libc::O_RDONLY | libc::O_DIRECTORY, 0) {
+                self.scan_dir(&Rc::new(dir), &path, &mut pending, &mut scheduled);
+            }
+        }
+
+        scheduled
+    }
+
+    fn scan_dir(
+        &self,
+        dir: &Rc<File>,
+        dir_path: &Path,
+        pending: &mut Vec<(Rc<File>, PathBuf, CString)>,
+        scheduled: &mut Vec<(PathBuf, SystemTime, fs::Metadata)>,
+    ) {
+        let Ok(names) = names(dir) else {
+            return;
+        };
+
+        for name in names {
+            let path = dir_path.join(OsStr::from_bytes(name.to_bytes()));
+            let metadata = match open_at(dir, &name, libc::O_PATH, 0).and_then(|f| f.metadata()) {
+                Ok(metadata) => metadata,
+                Err(_) => continue,
+            };
+
+            if metadata.is_dir() {
+                if name.to_bytes() != TRASH_DIR.as_bytes()
+                    && !self
+                        .exclude
+                        .iter()
+                        .any(|e| e.recursive && e.pattern.matches_path_with(&path, MATCH_OPTIONS))
+                {
+                    pending.push((dir.clone(), path, name));
+                }
+            } else if metadata.is_file() && self.matches(&path) && !self.excluded(&path) {
+                if let Some(deadline) = self.deadline_of(&metadata) {
+                    scheduled.push((path, deadline, metadata));
+                }
+            }
+        }
+    }
+}
+
+/// The names in the directory `dir`, but `.` and `..`.
+fn names(dir: &File) -> io::Result<Vec<CString>> {
+    // The stream owns the fd it's given.
+    let fd = dir.try_clone()?.into_raw_fd();
+    let stream = unsafe { libc::fdopendir(fd) };
+    if stream.is_null() {
+        let err = io::Error::last_os_error();
+        unsafe { libc::close(fd) };
+        return Err(err);
+    }
+
+    let mut names = Vec::new();
+    loop {
+        let entry = unsafe { libc::readdir(stream) };
+        if entry.is_null() {
+            break;
+        }
+        let name = unsafe { CStr::from_ptr((*entry).d_name.as_ptr()) };
+        if name.to_bytes() != b"." && name.to_bytes() != b".." {
+            names.push(name.to_owned());
+        }
+    }
+    unsafe { libc::closedir(stream) };
+    Ok(names)
+}
+
+fn timestamp(metadata: &fs::Metadata, basis: AgeBasis) -> Option<SystemTime> {
+    let secs = match basis {
+        AgeBasis::Mtime => metadata.mtime(),
+        AgeBasis::Atime => metadata.atime(),
+        AgeBasis::Ctime => metadata.ctime(),
+        AgeBasis::Btime => return metadata.created().ok(),
+    };
+    u64::try_from(secs)
+        .ok()
+        .map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+/// Parses ages such as `30s`, `12h`, `10d` or `1w2d`. A bare number is
+/// taken as seconds.
+pub fn parse_age(age: &str) -> Result<Duration, String> {
+    let age = age.trim();
+    if age.is_empty() {
+        return Err("empty age".to_string());
+    }
+    if let Ok(secs) = age.parse::<u64>() {
+        return Ok(Duration::from_secs(secs));
+    }
+
+    let mut total = Duration::ZERO;
+    let mut rest = age;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if digits == 0 {
+            return Err(format!("invalid age `{age}`"));
+        }
+        let value: u64 = rest[..digits]
+            .parse()
+            .map_err(|_| format!("invalid age `{age}`"))?;
+        rest = &rest[digits..];
+
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let unit = match &rest[..unit_len] {
+            "us" | "usec" => Duration::from_micros(1),
+            "ms" | "msec" => Duration::from_millis(1),
+            "s" | "sec" => Duration::from_secs(1),
+            "m" | "min" => Duration::from_secs(60),
+            "h" | "hr" => Duration::from_secs(60 * 60),
+            "d" | "day" => Duration::from_secs(24 * 60 * 60),
+            "w" | "week" => Duration::from_secs(7 * 24 * 60 * 60),
+            unit => return Err(format!("unknown unit `{unit}` in age `{age}`")),
+        };
+        rest = &rest[unit_len..];
+
+        let too_large = || format!("age `{age}` is too large");
+        let value = u32::try_from(value).map_err(|_| too_large())?;
+        total = total.checked_add(unit * value).ok_or_else(too_large)?;
+    }
+
+    Ok(total)
+}
+
+fn parse_rule(raw: RawRule, origin: &Path, index: usize) -> Result<Rule, String> {
+    let name = raw
+        .name
+        .unwrap_or_else(|| format!("{}#{}", origin.display(), index));
+
+    let matcher = match (raw.path, raw.glob) {
+        (Some(path), None) => Matcher::Prefix(PathBuf::from(path)),
+        (None, Some(glob)) => Matcher::Glob(
+            Pattern::new(&glob).map_err(|e| format!("rule `{name}`: invalid glob: {e}"))?,
+        ),
+        _ => {
+            return Err(format!(
+                "rule `{name}`: exactly one of `path` or `glob` must be set"
+            ))
+        }
+    };
+
+    let max_age = parse_age(&raw.max_age).map_err(|e| format!("rule `{name}`: {e}"))?;
+
+    parse_action(&raw.action).map_err(|e| format!("rule `{name}`: {e}"))?;
+
+    let exclude = raw
+        .exclude
+        .iter()
+        .map(|p| {
+            Pattern::new(p)
+                .map(|pattern| Exclusion {
+                    pattern,
+                    recursive: true,
+                })
+                .map_err(|e| format!("rule `{name}`: invalid exclude: {e}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let rule = Rule {
+        name,
+        ages: raw.age.into_vec(),
+        max_age,
+        action: raw.action,
+        dry_run: raw.dry_run,
+        matcher,
+        exclude,
+    };
+
+    if rule.ages.is_empty() {
+        return Err(format!("rule `{}`: `age` can't be empty", rule.name));
+    }
+
+    if !rule.root().is_absolute() {
+        return Err(format!("rule `{}`: the path must be absolute", rule.name));
+    }
+
+    Ok(rule)
+}
+
+/// Parses a single rules file.
+pub fn parse_rules(contents: &str, origin: &Path) -> Result<Vec<Rule>, String> {
+    let file: RulesFile =
+        toml::from_str(contents).map_err(|e| format!("{}: {e}", origin.display()))?;
+
+    file.rule
+        .into_iter()
+        .enumerate()
+        .map(|(i, raw)| parse_rule(raw, origin, i))
+        .collect()
+}
+
+/// Loads every `*.toml` file in `dir`, in lexicographic order. A missing
+/// directory means there are no rules.
+pub fn load_rules(dir: &Path) -> Result<Vec<Rule>, String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("{}: {e}", dir.display())),
+    };
+
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    files.sort();
+
+    let mut rules = Vec::new();
+    for file in files {
+        let contents = fs::read_to_string(&file).map_err(|e| format!("{}: {e}", file.display()))?;
+        rules.extend(parse_rules(&contents, &file)?);
+    }
+
+    Ok(rules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn parse(toml: &str) -> Rule {
+        parse_rules(toml, Path::new("test.toml")).unwrap().remove(0)
+    }
+
+    #[test]
+    fn parses_bare_numbers_as_seconds() {
+        assert_eq!(parse_age("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_age(" 0 "), Ok(Duration::ZERO));
+    }
+
+    #[test]
+    fn combines_suffixes() {
+        let day = Duration::from_secs(24 * 60 * 60);
+        assert_eq!(parse_age("1w2d"), Ok(day * 9));
+        assert_eq!(parse_age("1d12h"), Ok(day + day / 2));
+        assert_eq!(parse_age("1h30min"), Ok(Duration::from_secs(90 * 60)));
+        assert_eq!(parse_age("1s500ms"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_age("10day"), Ok(day * 10));
+    }
+
+    #[test]
+    fn rejects_invalid_ages() {
+        for age in ["", "   ", "d", "5x", "-5", "1d-2h", "1.5h"] {
+            assert!(parse_age(age).is_err(), "{age:?}");
+        }
+    }
+
+    #[test]
+    fn rejects_ages_that_overflow() {
+        assert!(parse_age("4294967296s").is_err());
+        assert!(parse_age("99999999999999999999d").is_err());
+        assert!(parse_age(&"4294967295w".repeat(10_000)).is_err());
+    }
+
+    #[test]
+    fn globs_dont_cross_directories() {
+        let rule = parse(
+            r#"[[rule]]
+            glob = "/srv/data/*.log"
+            max_age = "1d""#,
+        );
+        assert!(rule.matches(Path::new("/srv/data/a.log")));
+        assert!(!rule.matches(Path::new("/srv/data/sub/a.log")));
+
+        let rule = parse(
+            r#"[[rule]]
+            glob = "/srv/**/*.log"
+            max_age = "1d""#,
+        );
+        assert!(rule.matches(Path::new("/srv/a.log")));
+        assert!(rule.matches(Path::new("/srv/data/sub/a.log")));
+    }
+
+    #[test]
+    fn excludes_paths_and_what_is_below() {
+        let rule = parse(
+            r#"[[rule]]
+            path = "/srv/data"
+            max_age = "1d"
+            exclude = ["/srv/data/keep", "/srv/data/**/important.log", "/srv/data/*.pid"]"#,
+        );
+        assert!(rule.excluded(Path::new("/srv/data/keep")));
+        assert!(rule.excluded(Path::new("/srv/data/keep/a/b.log")));
+        assert!(rule.excluded(Path::new("/srv/data/a/b/important.log")));
+        assert!(rule.excluded(Path::new("/srv/data/x.pid")));
+        assert!(!rule.excluded(Path::new("/srv/data/sub/x.pid")));
+        assert!(!rule.excluded(Path::new("/srv/data/keeper")));
+        assert!(rule.excluded(Path::new("/srv/data/.sweeper-trash/1-a.log")));
+    }
+
+    #[test]
+    fn scans_without_following_symlinks() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let outside_tmp = TempDir::new().unwrap();
+        let outside = outside_tmp.path();
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("sub/old"), b"").unwrap();
+        fs::create_dir_all(dir.join("keep")).unwrap();
+        fs::write(dir.join("keep/old"), b"").unwrap();
+        fs::write(outside.join("secret"), b"").unwrap();
+        std::os::unix::fs::symlink(outside, dir.join("link")).unwrap();
+        std::os::unix::fs::symlink(outside.join("secret"), dir.join("file-link")).unwrap();
+
+        let rule = parse(&format!(
+            r#"[[rule]]
+            path = "{}"
+            max_age = "0"
+            exclude = ["{}/keep"]"#,
+            dir.display(),
+            dir.display()
+        ));
+        let found: Vec<PathBuf> = rule.scan().into_iter().map(|(path, _, _)| path).collect();
+
+        assert_eq!(found, [dir.join("sub/old")]);
+    }
+}
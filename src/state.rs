@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Lifecycle of a scheduled expiration:
+///
+/// ```text
+/// pending -> deleting -> deleted
+///  | ^ ^        |   `---> failed
+///  | | `--------'  retry    |
+///  | `----------------------'
+///  `-> cancelled / skipped
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum State {
+    /// Waiting for its deadline.
+    #[default]
+    Pending,
+    /// The action is being carried out.
+    Deleting,
+    /// The action completed.
+    Deleted,
+    /// The action failed for good, or too many times.
+    Failed,
+    /// Superseded by a newer expiration, no longer covered by its rule, or
+    /// cancelled by hand.
+    Cancelled,
+    /// The file didn't pass the checks right before acting on it.
+    Skipped,
+}
+
+impl State {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            State::Pending => "pending",
+            State::Deleting => "deleting",
+            State::Deleted => "deleted",
+            State::Failed => "failed",
+            State::Cancelled => "cancelled",
+            State::Skipped => "skipped",
+        }
+    }
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for State {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(State::Pending),
+            "deleting" => Ok(State::Deleting),
+            "deleted" => Ok(State::Deleted),
+            "failed" => Ok(State::Failed),
+            "cancelled" => Ok(State::Cancelled),
+            "skipped" => Ok(State::Skipped),
+            s => Err(format!("unknown state `{s}`")),
+        }
+    }
+}
@@ -0,0 +1,513 @@
+use crate::escape;
+use crate::state::State;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub mod memory;
+pub mod observed;
+pub mod sqlite;
+
+pub use memory::MemoryStore;
+pub use observed::ObservedStore;
+pub use sqlite::SqliteStore;
+
+#[derive(Debug)]
+pub struct StoreError(String);
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+impl From<rusqlite::Error> for StoreError {
+    fn from(e: rusqlite::Error) -> Self {
+        StoreError(e.to_string())
+    }
+}
+
+impl From<String> for StoreError {
+    fn from(e: String) -> Self {
+        StoreError(e)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, StoreError>;
+
+pub fn epoch(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |d| i64::try_from(d.as_secs()).unwrap_or(i64::MAX))
+}
+
+/// Seconds since the epoch, which is how every timestamp is stored.
+pub fn now() -> i64 {
+    epoch(SystemTime::now())
+}
+
+/// A scheduled expiration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Entry {
+    pub id: Option<i64>,
+    #[serde(
+        serialize_with = "escape::serialize",
+        deserialize_with = "escape::deserialize"
+    )]
+    pub path: PathBuf,
+    /// The xattr that set the expiration, or the rule that matched.
+    pub name: String,
+    pub expire_at: i64,
+    /// Device and inode of the file when it was scheduled, to detect if it
+    /// was replaced since.
+    pub dev: Option<u64>,
+    pub ino: Option<u64>,
+    pub state: State,
+    /// Process that set the expiration, for xattr-driven entries.
+    pub pid: Option<u32>,
+    pub uid: Option<u32>,
+    pub comm: Option<String>,
+    pub action: Option<String>,
+    pub bytes_freed: Option<u64>,
+    pub error: Option<String>,
+    /// Failed attempts at expiring the file so far.
+    pub attempts: u32,
+    pub next_attempt_at: Option<i64>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Fields stored along with a state transition. `error` and
+/// `next_attempt_at` are always overwritten, the rest only when set.
+#[derive(Debug, Default)]
+pub struct Update<'a> {
+    pub action: Option<&'a str>,
+    pub bytes_freed: Option<u64>,
+    pub error: Option<&'a str>,
+    pub attempts: Option<u32>,
+    pub next_attempt_at: Option<i64>,
+}
+
+/// An entry of the audit trail.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transition {
+    pub entry_id: i64,
+    pub state: State,
+    pub at: i64,
+    pub error: Option<String>,
+}
+
+/// What dry-run mode would have done to a file.
+#[derive(Debug, Clone, Serialize)]
+pub struct DryRun {
+    #[serde(serialize_with = "escape::serialize")]
+    pub path: PathBuf,
+    pub name: String,
+    pub action: String,
+    pub size: Option<u64>,
+    pub reason: String,
+    pub first_seen: i64,
+    pub last_seen: i64,
+}
+
+/// A file moved to the trash of its mount, and where it came from.
+#[derive(Debug, Clone, Serialize)]
+pub struct Quarantined {
+    pub id: Option<i64>,
+    #[serde(serialize_with = "escape::serialize")]
+    pub path: PathBuf,
+    #[serde(serialize_with = "escape::serialize")]
+    pub trash_path: PathBuf,
+    pub uid: u32,
+    pub gid: u32,
+    pub mode: u32,
+    pub quarantined_at: i64,
+    pub purge_at: i64,
+}
+
+/// Where the scheduled expirations, their history and the quarantined
+/// files are kept. Every method is atomic on its own.
+pub trait Store: Send {
+    /// Adds a pending entry, returning its id.
+    fn insert(&self, entry: &Entry) -> Result<i64>;
+
+    /// Adds a batch of pending entries at once, each replacing the pending
+    /// entries for the same file and xattr, which are cancelled with
+    /// `reason`. Returns their ids.
+    fn ingest(&self, entries: &[Entry], reason: &str) -> Result<Vec<i64>>;
+
+    /// Moves the pending entry for `path` and `name` to `expire_at`, or adds
+    /// one if there's none, unless the file was already handled for that
+    /// very deadline, e.g. skipped in dry-run mode or failed. The entry is
+    /// for the file with device `dev` and inode `ino`.
+    fn schedule(&self, path: &Path, name: &str, expire_at: i64, dev: u64, ino: u64) -> Result<()>;
+
+    /// Moves the deadline of an entry.
+    fn reschedule(&self, id: i64, expire_at: i64) -> Result<()>;
+
+    /// Cancels the pending entries for `path` and `name`. Returns how many
+    /// there were.
+    fn cancel(&self, path: &Path, name: &str, reason: &str) -> Result<usize>;
+
+    /// Pending entries whose deadline has passed and that aren't waiting
+    /// for a retry, earliest first.
+    fn next_due(&self, limit: usize) -> Result<Vec<Entry>>;
+
+    /// At most `limit` pending entries, earliest deadline first, optionally
+    /// only those due before `due_before` or under `root`.
+    fn pending(
+        &self,
+        due_before: Option<i64>,
+        root: Option<&Path>,
+        limit: usize,
+    ) -> Result<Vec<Entry>>;
+
+    /// Entries in `state`, most recently updated first.
+    fn entries(&self, state: State) -> Result<Vec<Entry>>;
+
+    fn entry(&self, id: i64) -> Result<Option<Entry>>;
+
+    /// Every entry for `path`, whatever their state, most recent first.
+    fn find(&self, path: &Path) -> Result<Vec<Entry>>;
+
+    /// Moves an entry from `from` to `to` and logs it, only if the entry is
+    /// still in `from`, so concurrent changes aren't overwritten. Returns
+    /// whether the transition happened.
+    fn transition(&self, id: i64, from: State, to: State, update: Update) -> Result<bool>;
+
+    /// Every state the entry went through, oldest first.
+    fn history(&self, id: i64) -> Result<Vec<Transition>>;
+
+    /// The last `limit` transitions of any entry, most recent first.
+    fn recent_transitions(&self, limit: usize) -> Result<Vec<Transition>>;
+
+    /// Records what dry-run mode would have done to a file, once per file
+    /// and action.
+    fn record_dry_run(
+        &self,
+        path: &Path,
+        name: &str,
+        action: &str,
+        size: Option<u64>,
+        reason: &str,
+    ) -> Result<()>;
+
+    /// The dry-run records, most recent first.
+    fn dry_runs(&self) -> Result<Vec<DryRun>>;
+
+    /// Remembers a quarantined file, returning its id.
+    fn quarantine(&self, quarantined: &Quarantined) -> Result<i64>;
+
+    /// A file still in quarantine, by id.
+    fn quarantined(&self, id: i64) -> Result<Option<Quarantined>>;
+
+    /// The file most recently quarantined from `path`, if still there.
+    fn last_quarantined(&self, path: &Path) -> Result<Option<Quarantined>>;
+
+    /// Quarantined files whose grace period is over.
+    fn purgeable(&self) -> Result<Vec<Quarantined>>;
+
+    fn mark_purged(&self, id: i64) -> Result<()>;
+
+    fn mark_restored(&self, id: i64) -> Result<()>;
+
+    /// Drops a quarantine recorded for a move that didn't happen.
+    fn forget_quarantined(&self, id: i64) -> Result<()>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+    use tempfile::TempDir;
+
+    /// Runs `check` against every backend, which must behave the same.
+    fn each_store(check: impl Fn(&dyn Store)) {
+        check(&MemoryStore::new());
+
+        let tmp = TempDir::new().unwrap();
+        check(&SqliteStore::open(&tmp.path().join("sweeper.db")).unwrap());
+    }
+
+    fn pending(path: &str, expire_at: i64) -> Entry {
+        Entry {
+            path: PathBuf::from(path),
+            name: "user.expire_at".to_string(),
+            expire_at,
+            ..Entry::default()
+        }
+    }
+
+    fn quarantined(path: &str, purge_at: i64) -> Quarantined {
+        Quarantined {
+            id: None,
+            path: PathBuf::from(path),
+            trash_path: PathBuf::from("/.sweeper-trash/1"),
+            uid: 1000,
+            gid: 1000,
+            mode: 0o100644,
+            quarantined_at: now(),
+            purge_at,
+        }
+    }
+
+    #[test]
+    fn keeps_what_was_inserted() {
+        each_store(|store| {
+            let path = PathBuf::from(OsStr::from_bytes(b"/tmp/caf\xe9"));
+            let id = store
+                .insert(&Entry {
+                    path: path.clone(),
+                    dev: Some(8),
+                    ino: Some(42),
+                    pid: Some(7),
+                    uid: Some(1000),
+                    comm: Some("touch".to_string()),
+                    ..pending("", 100)
+                })
+                .unwrap();
+
+            let entry = store.entry(id).unwrap().unwrap();
+            assert_eq!(entry.id, Some(id));
+            assert_eq!(entry.path, path);
+            assert_eq!(entry.expire_at, 100);
+            assert_eq!((entry.dev, entry.ino), (Some(8), Some(42)));
+            assert_eq!(entry.state, State::Pending);
+            assert_eq!(entry.pid, Some(7));
+            assert_eq!(entry.comm.as_deref(), Some("touch"));
+            assert_eq!(store.find(&path).unwrap().len(), 1);
+            assert!(store.entry(id + 1).unwrap().is_none());
+
+            let history = store.history(id).unwrap();
+            assert_eq!(history.len(), 1);
+            assert_eq!(history[0].state, State::Pending);
+        });
+    }
+
+    #[test]
+    fn hands_out_due_entries_earliest_first() {
+        each_store(|store| {
+            let now = now();
+            store.insert(&pending("/tmp/later", now - 10)).unwrap();
+            store.insert(&pending("/tmp/first", now - 20)).unwrap();
+            store.insert(&pending("/tmp/future", now + 60)).unwrap();
+            let retried = store.insert(&pending("/tmp/retried", now - 30)).unwrap();
+            let update = Update {
+                attempts: Some(1),
+                next_attempt_at: Some(now + 60),
+                ..Update::default()
+            };
+            assert!(store
+                .transition(retried, State::Pending, State::Pending, update)
+                .unwrap());
+
+            let due: Vec<PathBuf> = store
+                .next_due(10)
+                .unwrap()
+                .into_iter()
+                .map(|e| e.path)
+                .collect();
+            assert_eq!(due, [Path::new("/tmp/first"), Path::new("/tmp/later")]);
+            assert_eq!(store.next_due(1).unwrap().len(), 1);
+        });
+    }
+
+    #[test]
+    fn lists_pending_entries_under_a_root() {
+        each_store(|store| {
+            store.insert(&pending("/tmp/b/late", 300)).unwrap();
+            store.insert(&pending("/tmp/b", 200)).unwrap();
+            store.insert(&pending("/tmp/a", 100)).unwrap();
+            store.insert(&pending("/tmp/bb", 50)).unwrap();
+            let done = store.insert(&pending("/tmp/b/done", 0)).unwrap();
+            store
+                .transition(done, State::Pending, State::Deleted, Update::default())
+                .unwrap();
+
+            let paths = |due_before, root: Option<&str>, limit| -> Vec<PathBuf> {
+                store
+                    .pending(due_before, root.map(Path::new), limit)
+                    .unwrap()
+                    .into_iter()
+                    .map(|e| e.path)
+                    .collect()
+            };
+            assert_eq!(
+                paths(None, None, 10),
+                ["/tmp/bb", "/tmp/a", "/tmp/b", "/tmp/b/late"].map(PathBuf::from)
+            );
+            assert_eq!(
+                paths(None, None, 2),
+                ["/tmp/bb", "/tmp/a"].map(PathBuf::from)
+            );
+            assert_eq!(
+                paths(Some(200), None, 10),
+                ["/tmp/bb", "/tmp/a"].map(PathBuf::from)
+            );
+            assert_eq!(
+                paths(None, Some("/tmp/b/"), 10),
+                ["/tmp/b", "/tmp/b/late"].map(PathBuf::from)
+            );
+            assert_eq!(paths(None, Some("/"), 10).len(), 4);
+        });
+    }
+
+    #[test]
+    fn transitions_only_from_the_current_state() {
+        each_store(|store| {
+            let id = store.insert(&pending("/tmp/file", 0)).unwrap();
+            let update = Update {
+                action: Some("delete"),
+                bytes_freed: Some(12),
+                ..Update::default()
+            };
+            assert!(store
+                .transition(id, State::Pending, State::Deleted, update)
+                .unwrap());
+            assert!(!store
+                .transition(id, State::Pending, State::Failed, Update::default())
+                .unwrap());
+
+            let entry = store.entry(id).unwrap().unwrap();
+            assert_eq!(entry.state, State::Deleted);
+            assert_eq!(entry.action.as_deref(), Some("delete"));
+            assert_eq!(entry.bytes_freed, Some(12));
+            assert_eq!(store.entries(State::Deleted).unwrap().len(), 1);
+            assert!(store.entries(State::Failed).unwrap().is_empty());
+
+            let states: Vec<State> = store
+                .history(id)
+                .unwrap()
+                .into_iter()
+                .map(|t| t.state)
+                .collect();
+            assert_eq!(states, [State::Pending, State::Deleted]);
+            assert_eq!(
+                store.recent_transitions(1).unwrap()[0].state,
+                State::Deleted
+            );
+        });
+    }
+
+    #[test]
+    fn ingesting_replaces_pending_entries() {
+        each_store(|store| {
+            let old = store.insert(&pending("/tmp/file", 100)).unwrap();
+            let other = store.insert(&pending("/tmp/other", 100)).unwrap();
+            let ids = store
+                .ingest(&[pending("/tmp/file", 200)], "replaced")
+                .unwrap();
+
+            let old = store.entry(old).unwrap().unwrap();
+            assert_eq!(old.state, State::Cancelled);
+            assert_eq!(old.error.as_deref(), Some("replaced"));
+            assert_eq!(store.entry(ids[0]).unwrap().unwrap().expire_at, 200);
+            assert_eq!(store.entry(other).unwrap().unwrap().state, State::Pending);
+
+            assert_eq!(
+                store
+                    .cancel(Path::new("/tmp/file"), "user.expire_at", "gone")
+                    .unwrap(),
+                1
+            );
+            assert_eq!(
+                store
+                    .cancel(Path::new("/tmp/file"), "user.expire_at", "gone")
+                    .unwrap(),
+                0
+            );
+        });
+    }
+
+    #[test]
+    fn schedules_each_deadline_once() {
+        each_store(|store| {
+            let path = Path::new("/tmp/file");
+            store.schedule(path, "rule", 100, 1, 2).unwrap();
+            store.schedule(path, "rule", 200, 1, 3).unwrap();
+            let entries = store.find(path).unwrap();
+            assert_eq!(entries.len(), 1);
+            assert_eq!(entries[0].expire_at, 200);
+            assert_eq!((entries[0].dev, entries[0].ino), (Some(1), Some(3)));
+
+            let id = entries[0].id.unwrap();
+            store
+                .transition(id, State::Pending, State::Skipped, Update::default())
+                .unwrap();
+            store.schedule(path, "rule", 200, 1, 3).unwrap();
+            assert_eq!(store.find(path).unwrap().len(), 1);
+
+            store.schedule(path, "rule", 300, 1, 3).unwrap();
+            let entries = store.find(path).unwrap();
+            assert_eq!(entries.len(), 2);
+            assert_eq!(entries[0].state, State::Pending);
+            assert_eq!(entries[0].expire_at, 300);
+
+            store.reschedule(id, 400).unwrap();
+            assert_eq!(store.entry(id).unwrap().unwrap().expire_at, 400);
+        });
+    }
+
+    #[test]
+    fn records_dry_runs_once_per_action() {
+        each_store(|store| {
+            let path = Path::new("/tmp/file");
+            store
+                .record_dry_run(path, "rule", "delete", Some(1), "expired")
+                .unwrap();
+            store
+                .record_dry_run(path, "rule", "delete", Some(2), "still expired")
+                .unwrap();
+            store
+                .record_dry_run(path, "rule", "truncate", None, "expired")
+                .unwrap();
+
+            let dry_runs = store.dry_runs().unwrap();
+            assert_eq!(dry_runs.len(), 2);
+            let delete = dry_runs.iter().find(|d| d.action == "delete").unwrap();
+            assert_eq!(delete.size, Some(2));
+            assert_eq!(delete.reason, "still expired");
+        });
+    }
+
+    #[test]
+    fn tracks_quarantined_files() {
+        each_store(|store| {
+            let path = Path::new("/tmp/file");
+            let purged = store.quarantine(&quarantined("/tmp/file", 0)).unwrap();
+            let restored = store.quarantine(&quarantined("/tmp/file", 0)).unwrap();
+            let kept = store
+                .quarantine(&quarantined("/tmp/other", now() + 60))
+                .unwrap();
+            let forgotten = store.quarantine(&quarantined("/tmp/gone", 0)).unwrap();
+
+            assert_eq!(store.quarantined(kept).unwrap().unwrap().id, Some(kept));
+            assert_eq!(
+                store.last_quarantined(path).unwrap().unwrap().id,
+                Some(restored)
+            );
+            let mut purgeable: Vec<i64> = store
+                .purgeable()
+                .unwrap()
+                .into_iter()
+                .filter_map(|q| q.id)
+                .collect();
+            purgeable.sort();
+            assert_eq!(purgeable, [purged, restored, forgotten]);
+
+            store.mark_restored(restored).unwrap();
+            assert_eq!(
+                store.last_quarantined(path).unwrap().unwrap().id,
+                Some(purged)
+            );
+            store.mark_purged(purged).unwrap();
+            store.forget_quarantined(forgotten).unwrap();
+            assert!(store.last_quarantined(path).unwrap().is_none());
+            assert!(store.quarantined(forgotten).unwrap().is_none());
+            assert!(store.purgeable().unwrap().is_empty());
+        });
+    }
+}
@@ -0,0 +1,371 @@
+use crate::state::State;
+use crate::store::{now, DryRun, Entry, Quarantined, Result, Store, Transition, Update};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+#[derive(Debug)]
+struct QuarantineRecord {
+    quarantined: Quarantined,
+    restored_at: Option<i64>,
+    purged_at: Option<i64>,
+}
+
+impl QuarantineRecord {
+    fn is_active(&self) -> bool {
+        self.restored_at.is_none() && self.purged_at.is_none()
+    }
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    last_id: i64,
+    entries: BTreeMap<i64, Entry>,
+    transitions: Vec<Transition>,
+    dry_runs: Vec<DryRun>,
+    last_quarantine_id: i64,
+    quarantine: BTreeMap<i64, QuarantineRecord>,
+}
+
+impl Inner {
+    fn log(&mut self, id: i64, state: State, error: Option<&str>) {
+        self.transitions.push(Transition {
+            entry_id: id,
+            state,
+            at: now(),
+            error: error.map(str::to_string),
+        });
+    }
+
+    fn insert(&mut self, entry: &Entry) -> i64 {
+        self.last_id += 1;
+        let id = self.last_id;
+        let now = now();
+        self.entries.insert(
+            id,
+            Entry {
+                id: Some(id),
+                state: State::Pending,
+                created_at: now,
+                updated_at: now,
+                ..entry.clone()
+            },
+        );
+        self.log(id, State::Pending, None);
+        id
+    }
+
+    fn cancel(&mut self, path: &Path, name: &str, reason: &str) -> usize {
+        let ids: Vec<i64> = self
+            .entries
+            .values()
+            .filter(|e| e.path == path && e.name == name && e.state == State::Pending)
+            .filter_map(|e| e.id)
+            .collect();
+        for &id in &ids {
+            let update = Update {
+                error: Some(reason),
+                ..Update::default()
+            };
+            self.transition(id, State::Pending, State::Cancelled, update);
+        }
+        ids.len()
+    }
+
+    fn transition(&mut self, id: i64, from: State, to: State, update: Update) -> bool {
+        let entry = match self.entries.get_mut(&id) {
+            Some(entry) if entry.state == from => entry,
+            _ => return false,
+        };
+        entry.state = to;
+        entry.error = update.error.map(str::to_string);
+        if let Some(action) = update.action {
+            entry.action = Some(action.to_string());
+        }
+        if let Some(bytes_freed) = update.bytes_freed {
+            entry.bytes_freed = Some(bytes_freed);
+        }
+        if let Some(attempts) = update.attempts {
+            entry.attempts = attempts;
+        }
+        entry.next_attempt_at = update.next_attempt_at;
+        entry.updated_at = now();
+        self.log(id, to, update.error);
+        true
+    }
+}
+
+/// Keeps everything in memory, for tests and hosts where expirations don't
+/// need to survive a restart. Clones share the same data, so the ingestion
+/// and the cleaner can each have one. Lookups go through every entry, which
+/// is fine for the number of files such hosts deal with.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStore {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        MemoryStore::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        // The data is kept consistent by every method, even if another
+        // thread panicked while holding the lock.
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Store for MemoryStore {
+    fn insert(&self, entry: &Entry) -> Result<i64> {
+        Ok(self.lock().insert(entry))
+    }
+
+    fn ingest(&self, entries: &[Entry], reason: &str) -> Result<Vec<i64>> {
+        let mut inner = self.lock();
+        let mut ids = Vec::with_capacity(entries.len());
+        for entry in entries {
+            inner.cancel(&entry.path, &entry.name, reason);
+            ids.push(inner.insert(entry));
+        }
+        Ok(ids)
+    }
+
+    fn schedule(&self, path: &Path, name: &str, expire_at: i64, dev: u64, ino: u64) -> Result<()> {
+        let mut inner = self.lock();
+        let mut handled = false;
+        let mut updated = false;
+        for entry in inner.entries.values_mut() {
+            if entry.path != path || entry.name != name {
+                continue;
+            }
+            if entry.state == State::Pending {
+                entry.expire_at = expire_at;
+                entry.dev = Some(dev);
+                entry.ino = Some(ino);
+                entry.updated_at = now();
+                updated = true;
+            } else if entry.expire_at == expire_at {
+                handled = true;
+            }
+        }
+        if !updated && !handled {
+            inner.insert(&Entry {
+                path: path.to_path_buf(),
+                name: name.to_string(),
+                expire_at,
+                dev: Some(dev),
+                ino: Some(ino),
+                ..Entry::default()
+            });
+        }
+        Ok(())
+    }
+
+    fn reschedule(&self, id: i64, expire_at: i64) -> Result<()> {
+        if let Some(entry) = self.lock().entries.get_mut(&id) {
+            entry.expire_at = expire_at;
+            entry.updated_at = now();
+        }
+        Ok(())
+    }
+
+    fn cancel(&self, path: &Path, name: &str, reason: &str) -> Result<usize> {
+        Ok(self.lock().cancel(path, name, reason))
+    }
+
+    fn next_due(&self, limit: usize) -> Result<Vec<Entry>> {
+        let now = now();
+        let mut due: Vec<Entry> = self
+            .lock()
+            .entries
+            .values()
+            .filter(|e| e.state == State::Pending && e.expire_at <= now)
+            .filter(|e| e.next_attempt_at.is_none_or(|at| at <= now))
+            .cloned()
+            .collect();
+        due.sort_by_key(|e| e.expire_at);
+        due.truncate(limit);
+        Ok(due)
+    }
+
+    fn pending(
+        &self,
+        due_before: Option<i64>,
+        root: Option<&Path>,
+        limit: usize,
+    ) -> Result<Vec<Entry>> {
+        let mut pending: Vec<Entry> = self
+            .lock()
+            .entries
+            .values()
+            .filter(|e| e.state == State::Pending)
+            .filter(|e| due_before.is_none_or(|time| e.expire_at < time))
+            .filter(|e| root.is_none_or(|root| e.path.starts_with(root)))
+            .cloned()
+            .collect();
+        pending.sort_by_key(|e| (e.expire_at, e.id));
+        pending.truncate(limit);
+        Ok(pending)
+    }
+
+    fn entries(&self, state: State) -> Result<Vec<Entry>> {
+        let mut entries: Vec<Entry> = self
+            .lock()
+            .entries
+            .values()
+            .filter(|e| e.state == state)
+            .cloned()
+            .collect();
+        entries.sort_by_key(|e| std::cmp::Reverse((e.updated_at, e.id)));
+        Ok(entries)
+    }
+
+    fn entry(&self, id: i64) -> Result<Option<Entry>> {
+        Ok(self.lock().entries.get(&id).cloned())
+    }
+
+    fn find(&self, path: &Path) -> Result<Vec<Entry>> {
+        Ok(self
+            .lock()
+            .entries
+            .values()
+            .rev()
+            .filter(|e| e.path == path)
+            .cloned()
+            .collect())
+    }
+
+    fn transition(&self, id: i64, from: State, to: State, update: Update) -> Result<bool> {
+        Ok(self.lock().transition(id, from, to, update))
+    }
+
+    fn history(&self, id: i64) -> Result<Vec<Transition>> {
+        Ok(self
+            .lock()
+            .transitions
+            .iter()
+            .filter(|transition| transition.entry_id == id)
+            .cloned()
+            .collect())
+    }
+
+    fn recent_transitions(&self, limit: usize) -> Result<Vec<Transition>> {
+        Ok(self
+            .lock()
+            .transitions
+            .iter()
+            .rev()
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    fn record_dry_run(
+        &self,
+        path: &Path,
+        name: &str,
+        action: &str,
+        size: Option<u64>,
+        reason: &str,
+    ) -> Result<()> {
+        let mut inner = self.lock();
+        let now = now();
+        let existing = inner
+            .dry_runs
+            .iter_mut()
+            .find(|d| d.path == path && d.name == name && d.action == action);
+        match existing {
+            Some(dry_run) => {
+                dry_run.size = size;
+                dry_run.reason = reason.to_string();
+                dry_run.last_seen = now;
+            }
+            None => inner.dry_runs.push(DryRun {
+                path: path.to_path_buf(),
+                name: name.to_string(),
+                action: action.to_string(),
+                size,
+                reason: reason.to_string(),
+                first_seen: now,
+                last_seen: now,
+            }),
+        }
+        Ok(())
+    }
+
+    fn dry_runs(&self) -> Result<Vec<DryRun>> {
+        let mut dry_runs = self.lock().dry_runs.clone();
+        // Stable, so the latest recorded comes first among equals.
+        dry_runs.reverse();
+        dry_runs.sort_by_key(|d| std::cmp::Reverse(d.last_seen));
+        Ok(dry_runs)
+    }
+
+    fn quarantine(&self, quarantined: &Quarantined) -> Result<i64> {
+        let mut inner = self.lock();
+        inner.last_quarantine_id += 1;
+        let id = inner.last_quarantine_id;
+        inner.quarantine.insert(
+            id,
+            QuarantineRecord {
+                quarantined: Quarantined {
+                    id: Some(id),
+                    ..quarantined.clone()
+                },
+                restored_at: None,
+                purged_at: None,
+            },
+        );
+        Ok(id)
+    }
+
+    fn quarantined(&self, id: i64) -> Result<Option<Quarantined>> {
+        Ok(self
+            .lock()
+            .quarantine
+            .get(&id)
+            .filter(|record| record.is_active())
+            .map(|record| record.quarantined.clone()))
+    }
+
+    fn last_quarantined(&self, path: &Path) -> Result<Option<Quarantined>> {
+        Ok(self
+            .lock()
+            .quarantine
+            .values()
+            .filter(|record| record.is_active() && record.quarantined.path == path)
+            .max_by_key(|record| (record.quarantined.quarantined_at, record.quarantined.id))
+            .map(|record| record.quarantined.clone()))
+    }
+
+    fn purgeable(&self) -> Result<Vec<Quarantined>> {
+        let now = now();
+        Ok(self
+            .lock()
+            .quarantine
+            .values()
+            .filter(|record| record.is_active() && record.quarantined.purge_at <= now)
+            .map(|record| record.quarantined.clone())
+            .collect())
+    }
+
+    fn mark_purged(&self, id: i64) -> Result<()> {
+        if let Some(record) = self.lock().quarantine.get_mut(&id) {
+            record.purged_at = Some(now());
+        }
+        Ok(())
+    }
+
+    fn mark_restored(&self, id: i64) -> Result<()> {
+        if let Some(record) = self.lock().quarantine.get_mut(&id) {
+            record.restored_at = Some(now());
+        }
+        Ok(())
+    }
+
+    fn forget_quarantined(&self, id: i64) -> Result<()> {
+        self.lock().quarantine.remove(&id);
+        Ok(())
+    }
+}
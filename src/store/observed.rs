@@ -0,0 +1,168 @@
+use crate::control::{Hub, Notification};
+use crate::state::State;
+use crate::store::{DryRun, Entry, Quarantined, Result, Store, Transition, Update};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Wraps a store to publish what happens to the expirations on a [`Hub`],
+/// whoever makes it happen.
+pub struct ObservedStore {
+    store: Box<dyn Store>,
+    hub: Arc<Hub>,
+}
+
+impl ObservedStore {
+    pub fn new(store: Box<dyn Store>, hub: Arc<Hub>) -> Self {
+        ObservedStore { store, hub }
+    }
+}
+
+impl Store for ObservedStore {
+    fn insert(&self, entry: &Entry) -> Result<i64> {
+        let id = self.store.insert(entry)?;
+        if self.hub.has_subscribers() {
+            self.hub.publish(Notification::Scheduled {
+                entry: Entry {
+                    id: Some(id),
+                    ..entry.clone()
+                },
+            });
+        }
+        Ok(id)
+    }
+
+    fn ingest(&self, entries: &[Entry], reason: &str) -> Result<Vec<i64>> {
+        let ids = self.store.ingest(entries, reason)?;
+        if self.hub.has_subscribers() {
+            for (entry, &id) in entries.iter().zip(&ids) {
+                self.hub.publish(Notification::Scheduled {
+                    entry: Entry {
+                        id: Some(id),
+                        ..entry.clone()
+                    },
+                });
+            }
+        }
+        Ok(ids)
+    }
+
+    fn schedule(&self, path: &Path, name: &str, expire_at: i64, dev: u64, ino: u64) -> Result<()> {
+        // Runs for every file covered by a rule on every scan, mostly to
+        // find it already scheduled, so it isn't worth a notification.
+        self.store.schedule(path, name, expire_at, dev, ino)
+    }
+
+    fn reschedule(&self, id: i64, expire_at: i64) -> Result<()> {
+        self.store.reschedule(id, expire_at)?;
+        self.hub.publish(Notification::Rescheduled {
+            entry_id: id,
+            expire_at,
+        });
+        Ok(())
+    }
+
+    fn cancel(&self, path: &Path, name: &str, reason: &str) -> Result<usize> {
+        let count = self.store.cancel(path, name, reason)?;
+        if count > 0 {
+            self.hub.publish(Notification::Cancelled {
+                path: path.to_path_buf(),
+                name: name.to_string(),
+                count,
+                reason: reason.to_string(),
+            });
+        }
+        Ok(count)
+    }
+
+    fn next_due(&self, limit: usize) -> Result<Vec<Entry>> {
+        self.store.next_due(limit)
+    }
+
+    fn pending(
+        &self,
+        due_before: Option<i64>,
+        root: Option<&Path>,
+        limit: usize,
+    ) -> Result<Vec<Entry>> {
+        self.store.pending(due_before, root, limit)
+    }
+
+    fn entries(&self, state: State) -> Result<Vec<Entry>> {
+        self.store.entries(state)
+    }
+
+    fn entry(&self, id: i64) -> Result<Option<Entry>> {
+        self.store.entry(id)
+    }
+
+    fn find(&self, path: &Path) -> Result<Vec<Entry>> {
+        self.store.find(path)
+    }
+
+    fn transition(&self, id: i64, from: State, to: State, update: Update) -> Result<bool> {
+        let error = update.error.map(str::to_string);
+        let moved = self.store.transition(id, from, to, update)?;
+        if moved && self.hub.has_subscribers() {
+            let path = self.store.entry(id)?.unwrap_or_default().path;
+            self.hub.publish(Notification::Changed {
+                entry_id: id,
+                path,
+                from,
+                to,
+                error,
+            });
+        }
+        Ok(moved)
+    }
+
+    fn history(&self, id: i64) -> Result<Vec<Transition>> {
+        self.store.history(id)
+    }
+
+    fn recent_transitions(&self, limit: usize) -> Result<Vec<Transition>> {
+        self.store.recent_transitions(limit)
+    }
+
+    fn record_dry_run(
+        &self,
+        path: &Path,
+        name: &str,
+        action: &str,
+        size: Option<u64>,
+        reason: &str,
+    ) -> Result<()> {
+        self.store.record_dry_run(path, name, action, size, reason)
+    }
+
+    fn dry_runs(&self) -> Result<Vec<DryRun>> {
+        self.store.dry_runs()
+    }
+
+    fn quarantine(&self, quarantined: &Quarantined) -> Result<i64> {
+        self.store.quarantine(quarantined)
+    }
+
+    fn quarantined(&self, id: i64) -> Result<Option<Quarantined>> {
+        self.store.quarantined(id)
+    }
+
+    fn last_quarantined(&self, path: &Path) -> Result<Option<Quarantined>> {
+        self.store.last_quarantined(path)
+    }
+
+    fn purgeable(&self) -> Result<Vec<Quarantined>> {
+        self.store.purgeable()
+    }
+
+    fn mark_purged(&self, id: i64) -> Result<()> {
+        self.store.mark_purged(id)
+    }
+
+    fn mark_restored(&self, id: i64) -> Result<()> {
+        self.store.mark_restored(id)
+    }
+
+    fn forget_quarantined(&self, id: i64) -> Result<()> {
+        self.store.forget_quarantined(id)
+    }
+}
@@ -0,0 +1,499 @@
+use crate::db;
+use crate::state::State;
+use crate::store::{DryRun, Entry, Quarantined, Result, Store, StoreError, Transition, Update};
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ValueRef};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::ffi::OsStr;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+
+const ENTRY_COLUMNS: &str = "id, path, name, expire_at, dev, ino, state, pid, uid, comm, action,
+    bytes_freed, error, attempts, next_attempt_at, created_at, updated_at";
+
+const QUARANTINE_COLUMNS: &str = "id, path, trash_path, uid, gid, mode, quarantined_at, purge_at";
+
+impl FromSql for State {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        value
+            .as_str()?
+            .parse()
+            .map_err(|e: String| FromSqlError::Other(e.into()))
+    }
+}
+
+/// Paths are stored as BLOBs, as they may not be UTF-8.
+fn blob(path: &Path) -> &[u8] {
+    path.as_os_str().as_bytes()
+}
+
+fn path(row: &Row, idx: usize) -> rusqlite::Result<PathBuf> {
+    match row.get_ref(idx)? {
+        ValueRef::Blob(bytes) | ValueRef::Text(bytes) => {
+            Ok(PathBuf::from(OsStr::from_bytes(bytes)))
+        }
+        value => Err(rusqlite::Error::InvalidColumnType(
+            idx,
+            "path".to_string(),
+            value.data_type(),
+        )),
+    }
+}
+
+fn entry(row: &Row) -> rusqlite::Result<Entry> {
+    Ok(Entry {
+        id: row.get(0)?,
+        path: path(row, 1)?,
+        name: row.get(2)?,
+        expire_at: row.get(3)?,
+        dev: row.get(4)?,
+        ino: row.get(5)?,
+        state: row.get(6)?,
+        pid: row.get(7)?,
+        uid: row.get(8)?,
+        comm: row.get(9)?,
+        action: row.get(10)?,
+        bytes_freed: row.get(11)?,
+        error: row.get(12)?,
+        attempts: row.get(13)?,
+        next_attempt_at: row.get(14)?,
+        created_at: row.get(15)?,
+        updated_at: row.get(16)?,
+    })
+}
+
+fn transition_row(row: &Row) -> rusqlite::Result<Transition> {
+    Ok(Transition {
+        entry_id: row.get(0)?,
+        state: row.get(1)?,
+        at: row.get(2)?,
+        error: row.get(3)?,
+    })
+}
+
+fn quarantined(row: &Row) -> rusqlite::Result<Quarantined> {
+    Ok(Quarantined {
+        id: row.get(0)?,
+        path: path(row, 1)?,
+        trash_path: path(row, 2)?,
+        uid: row.get(3)?,
+        gid: row.get(4)?,
+        mode: row.get(5)?,
+        quarantined_at: row.get(6)?,
+        purge_at: row.get(7)?,
+    })
+}
+
+/// The default store, a SQLite database on disk.
+pub struct SqliteStore {
+    conn: Connection,
+}
+
+impl SqliteStore {
+    /// Opens the database at `path`, see `db::open`.
+    pub fn open(path: &Path) -> Result<Self> {
+        Ok(SqliteStore {
+            conn: db::open(path).map_err(StoreError::from)?,
+        })
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+}
+
+// The helpers below are the building blocks of the `Store` methods. They
+// don't open transactions themselves, so several can share one.
+
+fn insert(conn: &Connection, entry: &Entry) -> rusqlite::Result<i64> {
+    conn.prepare_cached(
+        "INSERT INTO sweeper (path, name, expire_at, dev, ino, state, pid, uid, comm,
+            created_at, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9,
+            strftime('%s', 'now'), strftime('%s', 'now'))",
+    )?
+    .execute(params![
+        blob(&entry.path),
+        entry.name,
+        entry.expire_at,
+        entry.dev,
+        entry.ino,
+        State::Pending.as_str(),
+        entry.pid,
+        entry.uid,
+        entry.comm
+    ])?;
+    let id = conn.last_insert_rowid();
+    conn.prepare_cached(
+        "INSERT INTO transitions (entry_id, state, at) VALUES (?1, ?2, strftime('%s', 'now'))",
+    )?
+    .execute(params![id, State::Pending.as_str()])?;
+    Ok(id)
+}
+
+fn transition(
+    conn: &Connection,
+    id: i64,
+    from: State,
+    to: State,
+    update: &Update,
+) -> rusqlite::Result<bool> {
+    let updated = conn
+        .prepare_cached(
+            "UPDATE sweeper SET state = ?3, error = ?4, action = COALESCE(?5, action),
+                bytes_freed = COALESCE(?6, bytes_freed), attempts = COALESCE(?7, attempts),
+                next_attempt_at = ?8, updated_at = strftime('%s', 'now')
+                WHERE id = ?1 AND state = ?2",
+        )?
+        .execute(params![
+            id,
+            from.as_str(),
+            to.as_str(),
+            update.error,
+            update.action,
+            update.bytes_freed,
+            update.attempts,
+            update.next_attempt_at
+        ])?;
+    if updated == 0 {
+        return Ok(false);
+    }
+    conn.prepare_cached(
+        "INSERT INTO transitions (entry_id, state, at, error)
+            VALUES (?1, ?2, strftime('%s', 'now'), ?3)",
+    )?
+    .execute(params![id, to.as_str(), update.error])?;
+    Ok(true)
+}
+
+fn cancel(conn: &Connection, path: &Path, name: &str, reason: &str) -> rusqlite::Result<usize> {
+    let ids = conn
+        .prepare_cached("SELECT id FROM sweeper WHERE path = ?1 AND name = ?2 AND state = ?3")?
+        .query_map(params![blob(path), name, State::Pending.as_str()], |row| {
+            row.get(0)
+        })?
+        .collect::<rusqlite::Result<Vec<i64>>>()?;
+    let update = Update {
+        error: Some(reason),
+        ..Update::default()
+    };
+    let mut cancelled = 0;
+    for id in ids {
+        if transition(conn, id, State::Pending, State::Cancelled, &update)? {
+            cancelled += 1;
+        }
+    }
+    Ok(cancelled)
+}
+
+impl Store for SqliteStore {
+    fn insert(&self, entry: &Entry) -> Result<i64> {
+        let tx = self.conn.unchecked_transaction()?;
+        let id = insert(&tx, entry)?;
+        tx.commit()?;
+        Ok(id)
+    }
+
+    fn ingest(&self, entries: &[Entry], reason: &str) -> Result<Vec<i64>> {
+        let tx = self.conn.unchecked_transaction()?;
+        let mut ids = Vec::with_capacity(entries.len());
+        for entry in entries {
+            cancel(&tx, &entry.path, &entry.name, reason)?;
+            ids.push(insert(&tx, entry)?);
+        }
+        tx.commit()?;
+        Ok(ids)
+    }
+
+    fn schedule(&self, path: &Path, name: &str, expire_at: i64, dev: u64, ino: u64) -> Result<()> {
+        // So a concurrent `ingest` can't slip in between the lookups and
+        // the insert.
+        let tx = self.conn.unchecked_transaction()?;
+        let updated = tx
+            .prepare_cached(
+                "UPDATE sweeper SET expire_at = ?3, dev = ?5, ino = ?6,
+                    updated_at = strftime('%s', 'now')
+                    WHERE path = ?1 AND name = ?2 AND state = ?4",
+            )?
+            .execute(params![
+                blob(path),
+                name,
+                expire_at,
+                State::Pending.as_str(),
+                dev,
+                ino
+            ])?;
+        if updated == 0 {
+            let handled: bool = tx
+                .prepare_cached(
+                    "SELECT EXISTS (SELECT 1 FROM sweeper WHERE path = ?1 AND name = ?2 AND expire_at = ?3)",
+                )?
+                .query_row(params![blob(path), name, expire_at], |row| row.get(0))?;
+            if !handled {
+                insert(
+                    &tx,
+                    &Entry {
+                        path: path.to_path_buf(),
+                        name: name.to_string(),
+                        expire_at,
+                        dev: Some(dev),
+                        ino: Some(ino),
+                        ..Entry::default()
+                    },
+                )?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn reschedule(&self, id: i64, expire_at: i64) -> Result<()> {
+        self.conn
+            .prepare_cached(
+                "UPDATE sweeper SET expire_at = ?2, updated_at = strftime('%s', 'now')
+                    WHERE id = ?1",
+            )?
+            .execute(params![id, expire_at])?;
+        Ok(())
+    }
+
+    fn cancel(&self, path: &Path, name: &str, reason: &str) -> Result<usize> {
+        let tx = self.conn.unchecked_transaction()?;
+        let cancelled = cancel(&tx, path, name, reason)?;
+        tx.commit()?;
+        Ok(cancelled)
+    }
+
+    fn next_due(&self, limit: usize) -> Result<Vec<Entry>> {
+        let due = self
+            .conn
+            .prepare_cached(&format!(
+                "SELECT {ENTRY_COLUMNS} FROM sweeper
+                    WHERE state = ?1 AND expire_at <= strftime('%s', 'now')
+                    AND (next_attempt_at IS NULL OR next_attempt_at <= strftime('%s', 'now'))
+                    ORDER BY expire_at LIMIT ?2"
+            ))?
+            .query_map(params![State::Pending.as_str(), limit], entry)?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(due)
+    }
+
+    fn pending(
+        &self,
+        due_before: Option<i64>,
+        root: Option<&Path>,
+        limit: usize,
+    ) -> Result<Vec<Entry>> {
+        // Under `root` is either `root` itself or starting with `root/`,
+        // compared as bytes like the paths are stored.
+        let (root, prefix) = root
+            .map(|root| {
+                let root = blob(root);
+                let root = &root[..root.iter().rposition(|&b| b != b'/').map_or(0, |i| i + 1)];
+                (root.to_vec(), [root, b"/"].concat())
+            })
+            .unzip();
+        // Without a bound the range still goes through the deadline index.
+        let due_before = due_before.unwrap_or(i64::MAX);
+        let pending = self
+            .conn
+            .prepare_cached(&format!(
+                "SELECT {ENTRY_COLUMNS} FROM sweeper
+                    WHERE state = ?1 AND expire_at < ?2
+                    AND (?3 IS NULL OR path = ?3 OR substr(path, 1, length(?4)) = ?4)
+                    ORDER BY expire_at, id LIMIT ?5"
+            ))?
+            .query_map(
+                params![State::Pending.as_str(), due_before, root, prefix, limit],
+                entry,
+            )?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(pending)
+    }
+
+    fn entries(&self, state: State) -> Result<Vec<Entry>> {
+        let entries = self
+            .conn
+            .prepare_cached(&format!(
+                "SELECT {ENTRY_COLUMNS} FROM sweeper
+                    WHERE state = ?1 ORDER BY updated_at DESC, id DESC"
+            ))?
+            .query_map(params![state.as_str()], entry)?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(entries)
+    }
+
+    fn entry(&self, id: i64) -> Result<Option<Entry>> {
+        let found = self
+            .conn
+            .prepare_cached(&format!(
+                "SELECT {ENTRY_COLUMNS} FROM sweeper WHERE id = ?1"
+            ))?
+            .query_row(params![id], entry)
+            .optional()?;
+        Ok(found)
+    }
+
+    fn find(&self, path: &Path) -> Result<Vec<Entry>> {
+        let entries = self
+            .conn
+            .prepare_cached(&format!(
+                "SELECT {ENTRY_COLUMNS} FROM sweeper WHERE path = ?1 ORDER BY id DESC"
+            ))?
+            .query_map(params![blob(path)], entry)?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(entries)
+    }
+
+    fn transition(&self, id: i64, from: State, to: State, update: Update) -> Result<bool> {
+        let tx = self.conn.unchecked_transaction()?;
+        let moved = transition(&tx, id, from, to, &update)?;
+        tx.commit()?;
+        Ok(moved)
+    }
+
+    fn history(&self, id: i64) -> Result<Vec<Transition>> {
+        let history = self
+            .conn
+            .prepare_cached(
+                "SELECT entry_id, state, at, error FROM transitions
+                    WHERE entry_id = ?1 ORDER BY at, id",
+            )?
+            .query_map(params![id], transition_row)?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(history)
+    }
+
+    fn recent_transitions(&self, limit: usize) -> Result<Vec<Transition>> {
+        let recent = self
+            .conn
+            .prepare_cached(
+                "SELECT entry_id, state, at, error FROM transitions
+                    ORDER BY at DESC, id DESC LIMIT ?1",
+            )?
+            .query_map(params![limit], transition_row)?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(recent)
+    }
+
+    fn record_dry_run(
+        &self,
+        path: &Path,
+        name: &str,
+        action: &str,
+        size: Option<u64>,
+        reason: &str,
+    ) -> Result<()> {
+        self.conn
+            .prepare_cached(
+                "INSERT INTO dry_run_history (path, name, action, size, reason, first_seen, last_seen)
+                    VALUES (?1, ?2, ?3, ?4, ?5, strftime('%s', 'now'), strftime('%s', 'now'))
+                    ON CONFLICT (path, name, action) DO UPDATE
+                    SET size = excluded.size, reason = excluded.reason, last_seen = excluded.last_seen",
+            )?
+            .execute(params![blob(path), name, action, size, reason])?;
+        Ok(())
+    }
+
+    fn dry_runs(&self) -> Result<Vec<DryRun>> {
+        let dry_runs = self
+            .conn
+            .prepare_cached(
+                "SELECT path, name, action, size, reason, first_seen, last_seen
+                    FROM dry_run_history ORDER BY last_seen DESC, id DESC",
+            )?
+            .query_map(params![], |row| {
+                Ok(DryRun {
+                    path: path(row, 0)?,
+                    name: row.get(1)?,
+                    action: row.get(2)?,
+                    size: row.get(3)?,
+                    reason: row.get(4)?,
+                    first_seen: row.get(5)?,
+                    last_seen: row.get(6)?,
+                })
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(dry_runs)
+    }
+
+    fn quarantine(&self, quarantined: &Quarantined) -> Result<i64> {
+        self.conn
+            .prepare_cached(
+                "INSERT INTO quarantine (path, trash_path, uid, gid, mode, quarantined_at, purge_at)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )?
+            .execute(params![
+                blob(&quarantined.path),
+                blob(&quarantined.trash_path),
+                quarantined.uid,
+                quarantined.gid,
+                quarantined.mode,
+                quarantined.quarantined_at,
+                quarantined.purge_at
+            ])?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    fn quarantined(&self, id: i64) -> Result<Option<Quarantined>> {
+        let found = self
+            .conn
+            .prepare_cached(&format!(
+                "SELECT {QUARANTINE_COLUMNS} FROM quarantine
+                    WHERE restored_at IS NULL AND purged_at IS NULL AND id = ?1"
+            ))?
+            .query_row(params![id], quarantined)
+            .optional()?;
+        Ok(found)
+    }
+
+    fn last_quarantined(&self, path: &Path) -> Result<Option<Quarantined>> {
+        let found = self
+            .conn
+            .prepare_cached(&format!(
+                "SELECT {QUARANTINE_COLUMNS} FROM quarantine
+                    WHERE restored_at IS NULL AND purged_at IS NULL AND path = ?1
+                    ORDER BY quarantined_at DESC, id DESC LIMIT 1"
+            ))?
+            .query_row(params![blob(path)], quarantined)
+            .optional()?;
+        Ok(found)
+    }
+
+    fn purgeable(&self) -> Result<Vec<Quarantined>> {
+        let due = self
+            .conn
+            .prepare_cached(&format!(
+                "SELECT {QUARANTINE_COLUMNS} FROM quarantine
+                    WHERE restored_at IS NULL AND purged_at IS NULL
+                    AND purge_at <= strftime('%s', 'now')"
+            ))?
+            .query_map(params![], quarantined)?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(due)
+    }
+
+    fn mark_purged(&self, id: i64) -> Result<()> {
+        self.conn
+            .prepare_cached(
+                "UPDATE quarantine SET purged_at = strftime('%s', 'now') WHERE id = ?1",
+            )?
+            .execute(params![id])?;
+        Ok(())
+    }
+
+    fn mark_restored(&self, id: i64) -> Result<()> {
+        self.conn
+            .prepare_cached(
+                "UPDATE quarantine SET restored_at = strftime('%s', 'now') WHERE id = ?1",
+            )?
+            .execute(params![id])?;
+        Ok(())
+    }
+
+    fn forget_quarantined(&self, id: i64) -> Result<()> {
+        self.conn
+            .prepare_cached("DELETE FROM quarantine WHERE id = ?1")?
+            .execute(params![id])?;
+        Ok(())
+    }
+}
@@ -4,12 +4,25 @@
 #include <bpf/bpf_helpers.h>
 #include <bpf/bpf_tracing.h>
 
+// Bump along with `EVENT_VERSION` in event.rs when changing event_t, whose
+// layout is checked there when building.
+#define EVENT_VERSION 1
+
 struct event_t {
+  u16 version;
+  u16 size;
+  u32 pid;
+  u32 uid;
+  char comm[16];
   char path[50];
   char name[50];
   char value[50];
 };
 
+// Never used, it makes libbpf-cargo generate `event_t` in the skeleton,
+// which is what event.rs decodes records with.
+struct event_t _event = {0};
+
 struct {
   __uint(type, BPF_MAP_TYPE_HASH);
   __uint(max_entries, 4096);
@@ -28,6 +41,12 @@ struct {
 int set_attr_enter(struct trace_event_raw_sys_enter *ctx) {
   struct event_t event = {0};
 
+  event.version = EVENT_VERSION;
+  event.size = sizeof(event);
+  event.pid = bpf_get_current_pid_tgid() >> 32;
+  event.uid = bpf_get_current_uid_gid();
+  bpf_get_current_comm(event.comm, sizeof(event.comm));
+
   // We could validate them here for speed
   bpf_probe_read_user_str(event.path, sizeof(event.path), (void *)ctx->args[0]);
   bpf_probe_read_user_str(event.name, sizeof(event.name), (void *)ctx->args[1]);
@@ -1,678 +0,0 @@
-// SPDX-License-Identifier: (LGPL-2.1 OR BSD-2-Clause)
-//
-// THIS FILE IS AUTOGENERATED BY CARGO-LIBBPF-GEN!
-
-pub use self::imp::*;
-
-#[allow(dead_code)]
-#[allow(non_snake_case)]
-#[allow(non_camel_case_types)]
-#[allow(clippy::transmute_ptr_to_ref)]
-#[allow(clippy::upper_case_acronyms)]
-mod imp {
-    use libbpf_rs::libbpf_sys;
-
-    fn build_skel_config() -> libbpf_rs::Result<libbpf_rs::skeleton::ObjectSkeletonConfig<'static>>
-    {
-        let mut builder = libbpf_rs::skeleton::ObjectSkeletonConfigBuilder::new(DATA);
-        builder
-            .name("sweeper_bpf")
-            .map("storage", false)
-            .map("events", false)
-            .prog("sys_enter_setxattr")
-            .prog("sys_exit_setxattr")
-            .prog("sys_enter_lsetxattr")
-            .prog("sys_exit_lsetxattr");
-
-        builder.build()
-    }
-
-    #[derive(Default)]
-    pub struct SweeperSkelBuilder {
-        pub obj_builder: libbpf_rs::ObjectBuilder,
-    }
-
-    impl<'a> SweeperSkelBuilder {
-        pub fn open(mut self) -> libbpf_rs::Result<OpenSweeperSkel<'a>> {
-            let mut skel_config = build_skel_config()?;
-            let open_opts = self.obj_builder.opts(std::ptr::null());
-
-            let ret =
-                unsafe { libbpf_sys::bpf_object__open_skeleton(skel_config.get(), &open_opts) };
-            if ret != 0 {
-                return Err(libbpf_rs::Error::System(-ret));
-            }
-
-            let obj = unsafe { libbpf_rs::OpenObject::from_ptr(skel_config.object_ptr())? };
-
-            Ok(OpenSweeperSkel { obj, skel_config })
-        }
-
-        pub fn open_opts(
-            self,
-            open_opts: libbpf_sys::bpf_object_open_opts,
-        ) -> libbpf_rs::Result<OpenSweeperSkel<'a>> {
-            let mut skel_config = build_skel_config()?;
-
-            let ret =
-                unsafe { libbpf_sys::bpf_object__open_skeleton(skel_config.get(), &open_opts) };
-            if ret != 0 {
-                return Err(libbpf_rs::Error::System(-ret));
-            }
-
-            let obj = unsafe { libbpf_rs::OpenObject::from_ptr(skel_config.object_ptr())? };
-
-            Ok(OpenSweeperSkel { obj, skel_config })
-        }
-    }
-
-    pub struct OpenSweeperMaps<'a> {
-        inner: &'a libbpf_rs::OpenObject,
-    }
-
-    impl<'a> OpenSweeperMaps<'a> {
-        pub fn storage(&self) -> &libbpf_rs::OpenMap {
-            self.inner.map("storage").unwrap()
-        }
-
-        pub fn events(&self) -> &libbpf_rs::OpenMap {
-            self.inner.map("events").unwrap()
-        }
-    }
-
-    pub struct OpenSweeperMapsMut<'a> {
-        inner: &'a mut libbpf_rs::OpenObject,
-    }
-
-    impl<'a> OpenSweeperMapsMut<'a> {
-        pub fn storage(&mut self) -> &mut libbpf_rs::OpenMap {
-            self.inner.map_mut("storage").unwrap()
-        }
-
-        pub fn events(&mut self) -> &mut libbpf_rs::OpenMap {
-            self.inner.map_mut("events").unwrap()
-        }
-    }
-
-    pub struct OpenSweeperProgs<'a> {
-        inner: &'a libbpf_rs::OpenObject,
-    }
-
-    impl<'a> OpenSweeperProgs<'a> {
-        pub fn sys_enter_setxattr(&self) -> &libbpf_rs::OpenProgram {
-            self.inner.prog("sys_enter_setxattr").unwrap()
-        }
-
-        pub fn sys_exit_setxattr(&self) -> &libbpf_rs::OpenProgram {
-            self.inner.prog("sys_exit_setxattr").unwrap()
-        }
-
-        pub fn sys_enter_lsetxattr(&self) -> &libbpf_rs::OpenProgram {
-            self.inner.prog("sys_enter_lsetxattr").unwrap()
-        }
-
-        pub fn sys_exit_lsetxattr(&self) -> &libbpf_rs::OpenProgram {
-            self.inner.prog("sys_exit_lsetxattr").unwrap()
-        }
-    }
-
-    pub struct OpenSweeperProgsMut<'a> {
-        inner: &'a mut libbpf_rs::OpenObject,
-    }
-
-    impl<'a> OpenSweeperProgsMut<'a> {
-        pub fn sys_enter_setxattr(&mut self) -> &mut libbpf_rs::OpenProgram {
-            self.inner.prog_mut("sys_enter_setxattr").unwrap()
-        }
-
-        pub fn sys_exit_setxattr(&mut self) -> &mut libbpf_rs::OpenProgram {
-            self.inner.prog_mut("sys_exit_setxattr").unwrap()
-        }
-
-        pub fn sys_enter_lsetxattr(&mut self) -> &mut libbpf_rs::OpenProgram {
-            self.inner.prog_mut("sys_enter_lsetxattr").unwrap()
-        }
-
-        pub fn sys_exit_lsetxattr(&mut self) -> &mut libbpf_rs::OpenProgram {
-            self.inner.prog_mut("sys_exit_lsetxattr").unwrap()
-        }
-    }
-
-    pub struct OpenSweeperSkel<'a> {
-        pub obj: libbpf_rs::OpenObject,
-        skel_config: libbpf_rs::skeleton::ObjectSkeletonConfig<'a>,
-    }
-
-    impl<'a> OpenSweeperSkel<'a> {
-        pub fn load(mut self) -> libbpf_rs::Result<SweeperSkel<'a>> {
-            let ret = unsafe { libbpf_sys::bpf_object__load_skeleton(self.skel_config.get()) };
-            if ret != 0 {
-                return Err(libbpf_rs::Error::System(-ret));
-            }
-
-            let obj = unsafe { libbpf_rs::Object::from_ptr(self.obj.take_ptr())? };
-
-            Ok(SweeperSkel {
-                obj,
-                skel_config: self.skel_config,
-                links: SweeperLinks::default(),
-            })
-        }
-
-        pub fn progs(&self) -> OpenSweeperProgs {
-            OpenSweeperProgs { inner: &self.obj }
-        }
-
-        pub fn progs_mut(&mut self) -> OpenSweeperProgsMut {
-            OpenSweeperProgsMut {
-                inner: &mut self.obj,
-            }
-        }
-
-        pub fn maps(&self) -> OpenSweeperMaps {
-            OpenSweeperMaps { inner: &self.obj }
-        }
-
-        pub fn maps_mut(&mut self) -> OpenSweeperMapsMut {
-            OpenSweeperMapsMut {
-                inner: &mut self.obj,
-            }
-        }
-    }
-
-    pub struct SweeperMaps<'a> {
-        inner: &'a libbpf_rs::Object,
-    }
-
-    impl<'a> SweeperMaps<'a> {
-        pub fn storage(&self) -> &libbpf_rs::Map {
-            self.inner.map("storage").unwrap()
-        }
-
-        pub fn events(&self) -> &libbpf_rs::Map {
-            self.inner.map("events").unwrap()
-        }
-    }
-
-    pub struct SweeperMapsMut<'a> {
-        inner: &'a mut libbpf_rs::Object,
-    }
-
-    impl<'a> SweeperMapsMut<'a> {
-        pub fn storage(&mut self) -> &mut libbpf_rs::Map {
-            self.inner.map_mut("storage").unwrap()
-        }
-
-        pub fn events(&mut self) -> &mut libbpf_rs::Map {
-            self.inner.map_mut("events").unwrap()
-        }
-    }
-
-    pub struct SweeperProgs<'a> {
-        inner: &'a libbpf_rs::Object,
-    }
-
-    impl<'a> SweeperProgs<'a> {
-        pub fn sys_enter_setxattr(&self) -> &libbpf_rs::Program {
-            self.inner.prog("sys_enter_setxattr").unwrap()
-        }
-
-        pub fn sys_exit_setxattr(&self) -> &libbpf_rs::Program {
-            self.inner.prog("sys_exit_setxattr").unwrap()
-        }
-
-        pub fn sys_enter_lsetxattr(&self) -> &libbpf_rs::Program {
-            self.inner.prog("sys_enter_lsetxattr").unwrap()
-        }
-
-        pub fn sys_exit_lsetxattr(&self) -> &libbpf_rs::Program {
-            self.inner.prog("sys_exit_lsetxattr").unwrap()
-        }
-    }
-
-    pub struct SweeperProgsMut<'a> {
-        inner: &'a mut libbpf_rs::Object,
-    }
-
-    impl<'a> SweeperProgsMut<'a> {
-        pub fn sys_enter_setxattr(&mut self) -> &mut libbpf_rs::Program {
-            self.inner.prog_mut("sys_enter_setxattr").unwrap()
-        }
-
-        pub fn sys_exit_setxattr(&mut self) -> &mut libbpf_rs::Program {
-            self.inner.prog_mut("sys_exit_setxattr").unwrap()
-        }
-
-        pub fn sys_enter_lsetxattr(&mut self) -> &mut libbpf_rs::Program {
-            self.inner.prog_mut("sys_enter_lsetxattr").unwrap()
-        }
-
-        pub fn sys_exit_lsetxattr(&mut self) -> &mut libbpf_rs::Program {
-            self.inner.prog_mut("sys_exit_lsetxattr").unwrap()
-        }
-    }
-
-    #[derive(Default)]
-    pub struct SweeperLinks {
-        pub sys_enter_setxattr: Option<libbpf_rs::Link>,
-        pub sys_exit_setxattr: Option<libbpf_rs::Link>,
-        pub sys_enter_lsetxattr: Option<libbpf_rs::Link>,
-        pub sys_exit_lsetxattr: Option<libbpf_rs::Link>,
-    }
-
-    pub struct SweeperSkel<'a> {
-        pub obj: libbpf_rs::Object,
-        skel_config: libbpf_rs::skeleton::ObjectSkeletonConfig<'a>,
-        pub links: SweeperLinks,
-    }
-
-    unsafe impl<'a> Send for SweeperSkel<'a> {}
-    unsafe impl<'a> Sync for SweeperSkel<'a> {}
-
-    impl<'a> SweeperSkel<'a> {
-        pub fn progs(&self) -> SweeperProgs {
-            SweeperProgs { inner: &self.obj }
-        }
-
-        pub fn progs_mut(&mut self) -> SweeperProgsMut {
-            SweeperProgsMut {
-                inner: &mut self.obj,
-            }
-        }
-
-        pub fn maps(&self) -> SweeperMaps {
-            SweeperMaps { inner: &self.obj }
-        }
-
-        pub fn maps_mut(&mut self) -> SweeperMapsMut {
-            SweeperMapsMut {
-                inner: &mut self.obj,
-            }
-        }
-
-        pub fn attach(&mut self) -> libbpf_rs::Result<()> {
-            let ret = unsafe { libbpf_sys::bpf_object__attach_skeleton(self.skel_config.get()) };
-            if ret != 0 {
-                return Err(libbpf_rs::Error::System(-ret));
-            }
-
-            self.links = SweeperLinks {
-                sys_enter_setxattr: (|| {
-                    Ok(core::ptr::NonNull::new(self.skel_config.prog_link_ptr(0)?)
-                        .map(|ptr| unsafe { libbpf_rs::Link::from_ptr(ptr) }))
-                })()?,
-                sys_exit_setxattr: (|| {
-                    Ok(core::ptr::NonNull::new(self.skel_config.prog_link_ptr(1)?)
-                        .map(|ptr| unsafe { libbpf_rs::Link::from_ptr(ptr) }))
-                })()?,
-                sys_enter_lsetxattr: (|| {
-                    Ok(core::ptr::NonNull::new(self.skel_config.prog_link_ptr(2)?)
-                        .map(|ptr| unsafe { libbpf_rs::Link::from_ptr(ptr) }))
-                })()?,
-                sys_exit_lsetxattr: (|| {
-                    Ok(core::ptr::NonNull::new(self.skel_config.prog_link_ptr(3)?)
-                        .map(|ptr| unsafe { libbpf_rs::Link::from_ptr(ptr) }))
-                })()?,
-            };
-
-            Ok(())
-        }
-    }
-
-    const DATA: &[u8] = &[
-        127, 69, 76, 70, 2, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 247, 0, 1, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 248, 29, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 64, 0, 0, 0, 0,
-        0, 64, 0, 17, 0, 1, 0, 0, 46, 115, 116, 114, 116, 97, 98, 0, 46, 115, 121, 109, 116, 97,
-        98, 0, 46, 116, 101, 120, 116, 0, 116, 114, 97, 99, 101, 112, 111, 105, 110, 116, 47, 115,
-        121, 115, 99, 97, 108, 108, 115, 47, 115, 121, 115, 95, 101, 110, 116, 101, 114, 95, 115,
-        101, 116, 120, 97, 116, 116, 114, 0, 116, 114, 97, 99, 101, 112, 111, 105, 110, 116, 47,
-        115, 121, 115, 99, 97, 108, 108, 115, 47, 115, 121, 115, 95, 101, 120, 105, 116, 95, 115,
-        101, 116, 120, 97, 116, 116, 114, 0, 116, 114, 97, 99, 101, 112, 111, 105, 110, 116, 47,
-        115, 121, 115, 99, 97, 108, 108, 115, 47, 115, 121, 115, 95, 101, 110, 116, 101, 114, 95,
-        108, 115, 101, 116, 120, 97, 116, 116, 114, 0, 116, 114, 97, 99, 101, 112, 111, 105, 110,
-        116, 47, 115, 121, 115, 99, 97, 108, 108, 115, 47, 115, 121, 115, 95, 101, 120, 105, 116,
-        95, 108, 115, 101, 116, 120, 97, 116, 116, 114, 0, 46, 109, 97, 112, 115, 0, 108, 105, 99,
-        101, 110, 115, 101, 0, 115, 119, 101, 101, 112, 101, 114, 46, 98, 112, 102, 46, 99, 0, 76,
-        66, 66, 49, 95, 51, 0, 76, 66, 66, 51, 95, 51, 0, 76, 66, 66, 53, 95, 51, 0, 115, 101, 116,
-        95, 97, 116, 116, 114, 95, 101, 110, 116, 101, 114, 0, 115, 116, 111, 114, 97, 103, 101, 0,
-        115, 101, 116, 95, 97, 116, 116, 114, 95, 101, 120, 105, 116, 0, 101, 118, 101, 110, 116,
-        115, 0, 115, 121, 115, 95, 101, 110, 116, 101, 114, 95, 115, 101, 116, 120, 97, 116, 116,
-        114, 0, 115, 121, 115, 95, 101, 120, 105, 116, 95, 115, 101, 116, 120, 97, 116, 116, 114,
-        0, 115, 121, 115, 95, 101, 110, 116, 101, 114, 95, 108, 115, 101, 116, 120, 97, 116, 116,
-        114, 0, 115, 121, 115, 95, 101, 120, 105, 116, 95, 108, 115, 101, 116, 120, 97, 116, 116,
-        114, 0, 76, 73, 67, 69, 78, 83, 69, 0, 46, 114, 101, 108, 46, 116, 101, 120, 116, 0, 46,
-        114, 101, 108, 116, 114, 97, 99, 101, 112, 111, 105, 110, 116, 47, 115, 121, 115, 99, 97,
-        108, 108, 115, 47, 115, 121, 115, 95, 101, 110, 116, 101, 114, 95, 115, 101, 116, 120, 97,
-        116, 116, 114, 0, 46, 114, 101, 108, 116, 114, 97, 99, 101, 112, 111, 105, 110, 116, 47,
-        115, 121, 115, 99, 97, 108, 108, 115, 47, 115, 121, 115, 95, 101, 120, 105, 116, 95, 115,
-        101, 116, 120, 97, 116, 116, 114, 0, 46, 114, 101, 108, 116, 114, 97, 99, 101, 112, 111,
-        105, 110, 116, 47, 115, 121, 115, 99, 97, 108, 108, 115, 47, 115, 121, 115, 95, 101, 110,
-        116, 101, 114, 95, 108, 115, 101, 116, 120, 97, 116, 116, 114, 0, 46, 114, 101, 108, 116,
-        114, 97, 99, 101, 112, 111, 105, 110, 116, 47, 115, 121, 115, 99, 97, 108, 108, 115, 47,
-        115, 121, 115, 95, 101, 120, 105, 116, 95, 108, 115, 101, 116, 120, 97, 116, 116, 114, 0,
-        46, 66, 84, 70, 0, 46, 66, 84, 70, 46, 101, 120, 116, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 193, 0, 0, 0, 4, 0, 241, 255, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 207, 0, 0, 0, 0, 0, 3, 0, 96, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 3, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3, 0, 5, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 214, 0, 0, 0, 0, 0, 5, 0, 216, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3, 0, 6, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 3, 0, 7, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 221,
-        0, 0, 0, 0, 0, 7, 0, 216, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 228, 0, 0, 0, 18, 0,
-        3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 136, 1, 0, 0, 0, 0, 0, 0, 243, 0, 0, 0, 17, 0, 8, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 32, 0, 0, 0, 0, 0, 0, 0, 251, 0, 0, 0, 18, 0, 3, 0, 136, 1, 0, 0, 0, 0, 0,
-        0, 224, 0, 0, 0, 0, 0, 0, 0, 9, 1, 0, 0, 17, 0, 8, 0, 32, 0, 0, 0, 0, 0, 0, 0, 32, 0, 0, 0,
-        0, 0, 0, 0, 16, 1, 0, 0, 18, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 136, 1, 0, 0, 0, 0, 0, 0, 35,
-        1, 0, 0, 18, 0, 5, 0, 0, 0, 0, 0, 0, 0, 0, 0, 224, 0, 0, 0, 0, 0, 0, 0, 53, 1, 0, 0, 18, 0,
-        6, 0, 0, 0, 0, 0, 0, 0, 0, 0, 136, 1, 0, 0, 0, 0, 0, 0, 73, 1, 0, 0, 18, 0, 7, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 224, 0, 0, 0, 0, 0, 0, 0, 92, 1, 0, 0, 17, 0, 9, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        13, 0, 0, 0, 0, 0, 0, 0, 191, 22, 0, 0, 0, 0, 0, 0, 183, 1, 0, 0, 0, 0, 0, 0, 107, 26, 252,
-        255, 0, 0, 0, 0, 99, 26, 248, 255, 0, 0, 0, 0, 123, 26, 240, 255, 0, 0, 0, 0, 123, 26, 232,
-        255, 0, 0, 0, 0, 123, 26, 224, 255, 0, 0, 0, 0, 123, 26, 216, 255, 0, 0, 0, 0, 123, 26,
-        208, 255, 0, 0, 0, 0, 123, 26, 200, 255, 0, 0, 0, 0, 123, 26, 192, 255, 0, 0, 0, 0, 123,
-        26, 184, 255, 0, 0, 0, 0, 123, 26, 176, 255, 0, 0, 0, 0, 123, 26, 168, 255, 0, 0, 0, 0,
-        123, 26, 160, 255, 0, 0, 0, 0, 123, 26, 152, 255, 0, 0, 0, 0, 123, 26, 144, 255, 0, 0, 0,
-        0, 123, 26, 136, 255, 0, 0, 0, 0, 123, 26, 128, 255, 0, 0, 0, 0, 123, 26, 120, 255, 0, 0,
-        0, 0, 123, 26, 112, 255, 0, 0, 0, 0, 123, 26, 104, 255, 0, 0, 0, 0, 121, 99, 16, 0, 0, 0,
-        0, 0, 191, 167, 0, 0, 0, 0, 0, 0, 7, 7, 0, 0, 104, 255, 255, 255, 191, 113, 0, 0, 0, 0, 0,
-        0, 183, 2, 0, 0, 50, 0, 0, 0, 133, 0, 0, 0, 114, 0, 0, 0, 121, 99, 24, 0, 0, 0, 0, 0, 191,
-        161, 0, 0, 0, 0, 0, 0, 7, 1, 0, 0, 154, 255, 255, 255, 183, 2, 0, 0, 50, 0, 0, 0, 133, 0,
-        0, 0, 114, 0, 0, 0, 121, 99, 32, 0, 0, 0, 0, 0, 191, 161, 0, 0, 0, 0, 0, 0, 7, 1, 0, 0,
-        204, 255, 255, 255, 183, 2, 0, 0, 50, 0, 0, 0, 133, 0, 0, 0, 114, 0, 0, 0, 133, 0, 0, 0,
-        14, 0, 0, 0, 123, 10, 96, 255, 0, 0, 0, 0, 191, 162, 0, 0, 0, 0, 0, 0, 7, 2, 0, 0, 96, 255,
-        255, 255, 24, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 191, 115, 0, 0, 0, 0, 0, 0, 183,
-        4, 0, 0, 0, 0, 0, 0, 133, 0, 0, 0, 2, 0, 0, 0, 183, 0, 0, 0, 0, 0, 0, 0, 149, 0, 0, 0, 0,
-        0, 0, 0, 191, 22, 0, 0, 0, 0, 0, 0, 183, 0, 0, 0, 1, 0, 0, 0, 121, 97, 16, 0, 0, 0, 0, 0,
-        85, 1, 23, 0, 0, 0, 0, 0, 133, 0, 0, 0, 14, 0, 0, 0, 123, 10, 248, 255, 0, 0, 0, 0, 191,
-        162, 0, 0, 0, 0, 0, 0, 7, 2, 0, 0, 248, 255, 255, 255, 24, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 133, 0, 0, 0, 1, 0, 0, 0, 191, 4, 0, 0, 0, 0, 0, 0, 183, 0, 0, 0, 1, 0, 0, 0,
-        21, 4, 13, 0, 0, 0, 0, 0, 191, 97, 0, 0, 0, 0, 0, 0, 24, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 24, 3, 0, 0, 255, 255, 255, 255, 0, 0, 0, 0, 0, 0, 0, 0, 183, 5, 0, 0, 150, 0,
-        0, 0, 133, 0, 0, 0, 25, 0, 0, 0, 191, 162, 0, 0, 0, 0, 0, 0, 7, 2, 0, 0, 248, 255, 255,
-        255, 24, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 133, 0, 0, 0, 3, 0, 0, 0, 183, 0, 0,
-        0, 0, 0, 0, 0, 149, 0, 0, 0, 0, 0, 0, 0, 191, 22, 0, 0, 0, 0, 0, 0, 183, 1, 0, 0, 0, 0, 0,
-        0, 107, 26, 252, 255, 0, 0, 0, 0, 99, 26, 248, 255, 0, 0, 0, 0, 123, 26, 240, 255, 0, 0, 0,
-        0, 123, 26, 232, 255, 0, 0, 0, 0, 123, 26, 224, 255, 0, 0, 0, 0, 123, 26, 216, 255, 0, 0,
-        0, 0, 123, 26, 208, 255, 0, 0, 0, 0, 123, 26, 200, 255, 0, 0, 0, 0, 123, 26, 192, 255, 0,
-        0, 0, 0, 123, 26, 184, 255, 0, 0, 0, 0, 123, 26, 176, 255, 0, 0, 0, 0, 123, 26, 168, 255,
-        0, 0, 0, 0, 123, 26, 160, 255, 0, 0, 0, 0, 123, 26, 152, 255, 0, 0, 0, 0, 123, 26, 144,
-        255, 0, 0, 0, 0, 123, 26, 136, 255, 0, 0, 0, 0, 123, 26, 128, 255, 0, 0, 0, 0, 123, 26,
-        120, 255, 0, 0, 0, 0, 123, 26, 112, 255, 0, 0, 0, 0, 123, 26, 104, 255, 0, 0, 0, 0, 121,
-        99, 16, 0, 0, 0, 0, 0, 191, 167, 0, 0, 0, 0, 0, 0, 7, 7, 0, 0, 104, 255, 255, 255, 191,
-        113, 0, 0, 0, 0, 0, 0, 183, 2, 0, 0, 50, 0, 0, 0, 133, 0, 0, 0, 114, 0, 0, 0, 121, 99, 24,
-        0, 0, 0, 0, 0, 191, 161, 0, 0, 0, 0, 0, 0, 7, 1, 0, 0, 154, 255, 255, 255, 183, 2, 0, 0,
-        50, 0, 0, 0, 133, 0, 0, 0, 114, 0, 0, 0, 121, 99, 32, 0, 0, 0, 0, 0, 191, 161, 0, 0, 0, 0,
-        0, 0, 7, 1, 0, 0, 204, 255, 255, 255, 183, 2, 0, 0, 50, 0, 0, 0, 133, 0, 0, 0, 114, 0, 0,
-        0, 133, 0, 0, 0, 14, 0, 0, 0, 123, 10, 96, 255, 0, 0, 0, 0, 191, 162, 0, 0, 0, 0, 0, 0, 7,
-        2, 0, 0, 96, 255, 255, 255, 24, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 191, 115, 0,
-        0, 0, 0, 0, 0, 183, 4, 0, 0, 0, 0, 0, 0, 133, 0, 0, 0, 2, 0, 0, 0, 183, 0, 0, 0, 0, 0, 0,
-        0, 149, 0, 0, 0, 0, 0, 0, 0, 191, 22, 0, 0, 0, 0, 0, 0, 183, 0, 0, 0, 1, 0, 0, 0, 121, 97,
-        16, 0, 0, 0, 0, 0, 85, 1, 23, 0, 0, 0, 0, 0, 133, 0, 0, 0, 14, 0, 0, 0, 123, 10, 248, 255,
-        0, 0, 0, 0, 191, 162, 0, 0, 0, 0, 0, 0, 7, 2, 0, 0, 248, 255, 255, 255, 24, 1, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 133, 0, 0, 0, 1, 0, 0, 0, 191, 4, 0, 0, 0, 0, 0, 0, 183, 0,
-        0, 0, 1, 0, 0, 0, 21, 4, 13, 0, 0, 0, 0, 0, 191, 97, 0, 0, 0, 0, 0, 0, 24, 2, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 24, 3, 0, 0, 255, 255, 255, 255, 0, 0, 0, 0, 0, 0, 0, 0, 183,
-        5, 0, 0, 150, 0, 0, 0, 133, 0, 0, 0, 25, 0, 0, 0, 191, 162, 0, 0, 0, 0, 0, 0, 7, 2, 0, 0,
-        248, 255, 255, 255, 24, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 133, 0, 0, 0, 3, 0, 0,
-        0, 183, 0, 0, 0, 0, 0, 0, 0, 149, 0, 0, 0, 0, 0, 0, 0, 191, 22, 0, 0, 0, 0, 0, 0, 183, 1,
-        0, 0, 0, 0, 0, 0, 107, 26, 252, 255, 0, 0, 0, 0, 99, 26, 248, 255, 0, 0, 0, 0, 123, 26,
-        240, 255, 0, 0, 0, 0, 123, 26, 232, 255, 0, 0, 0, 0, 123, 26, 224, 255, 0, 0, 0, 0, 123,
-        26, 216, 255, 0, 0, 0, 0, 123, 26, 208, 255, 0, 0, 0, 0, 123, 26, 200, 255, 0, 0, 0, 0,
-        123, 26, 192, 255, 0, 0, 0, 0, 123, 26, 184, 255, 0, 0, 0, 0, 123, 26, 176, 255, 0, 0, 0,
-        0, 123, 26, 168, 255, 0, 0, 0, 0, 123, 26, 160, 255, 0, 0, 0, 0, 123, 26, 152, 255, 0, 0,
-        0, 0, 123, 26, 144, 255, 0, 0, 0, 0, 123, 26, 136, 255, 0, 0, 0, 0, 123, 26, 128, 255, 0,
-        0, 0, 0, 123, 26, 120, 255, 0, 0, 0, 0, 123, 26, 112, 255, 0, 0, 0, 0, 123, 26, 104, 255,
-        0, 0, 0, 0, 121, 99, 16, 0, 0, 0, 0, 0, 191, 167, 0, 0, 0, 0, 0, 0, 7, 7, 0, 0, 104, 255,
-        255, 255, 191, 113, 0, 0, 0, 0, 0, 0, 183, 2, 0, 0, 50, 0, 0, 0, 133, 0, 0, 0, 114, 0, 0,
-        0, 121, 99, 24, 0, 0, 0, 0, 0, 191, 161, 0, 0, 0, 0, 0, 0, 7, 1, 0, 0, 154, 255, 255, 255,
-        183, 2, 0, 0, 50, 0, 0, 0, 133, 0, 0, 0, 114, 0, 0, 0, 121, 99, 32, 0, 0, 0, 0, 0, 191,
-        161, 0, 0, 0, 0, 0, 0, 7, 1, 0, 0, 204, 255, 255, 255, 183, 2, 0, 0, 50, 0, 0, 0, 133, 0,
-        0, 0, 114, 0, 0, 0, 133, 0, 0, 0, 14, 0, 0, 0, 123, 10, 96, 255, 0, 0, 0, 0, 191, 162, 0,
-        0, 0, 0, 0, 0, 7, 2, 0, 0, 96, 255, 255, 255, 24, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 191, 115, 0, 0, 0, 0, 0, 0, 183, 4, 0, 0, 0, 0, 0, 0, 133, 0, 0, 0, 2, 0, 0, 0, 183, 0,
-        0, 0, 0, 0, 0, 0, 149, 0, 0, 0, 0, 0, 0, 0, 191, 22, 0, 0, 0, 0, 0, 0, 183, 0, 0, 0, 1, 0,
-        0, 0, 121, 97, 16, 0, 0, 0, 0, 0, 85, 1, 23, 0, 0, 0, 0, 0, 133, 0, 0, 0, 14, 0, 0, 0, 123,
-        10, 248, 255, 0, 0, 0, 0, 191, 162, 0, 0, 0, 0, 0, 0, 7, 2, 0, 0, 248, 255, 255, 255, 24,
-        1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 133, 0, 0, 0, 1, 0, 0, 0, 191, 4, 0, 0, 0, 0,
-        0, 0, 183, 0, 0, 0, 1, 0, 0, 0, 21, 4, 13, 0, 0, 0, 0, 0, 191, 97, 0, 0, 0, 0, 0, 0, 24, 2,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 24, 3, 0, 0, 255, 255, 255, 255, 0, 0, 0, 0, 0,
-        0, 0, 0, 183, 5, 0, 0, 150, 0, 0, 0, 133, 0, 0, 0, 25, 0, 0, 0, 191, 162, 0, 0, 0, 0, 0, 0,
-        7, 2, 0, 0, 248, 255, 255, 255, 24, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 133, 0, 0,
-        0, 3, 0, 0, 0, 183, 0, 0, 0, 0, 0, 0, 0, 149, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 68, 117, 97,
-        108, 32, 77, 73, 84, 47, 71, 80, 76, 0, 0, 0, 0, 80, 1, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 11,
-        0, 0, 0, 200, 1, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 11, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 1, 0,
-        0, 0, 13, 0, 0, 0, 64, 2, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 11, 0, 0, 0, 80, 1, 0, 0, 0, 0, 0,
-        0, 1, 0, 0, 0, 11, 0, 0, 0, 64, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 11, 0, 0, 0, 120, 0, 0, 0,
-        0, 0, 0, 0, 1, 0, 0, 0, 13, 0, 0, 0, 184, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 11, 0, 0, 0, 80,
-        1, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 11, 0, 0, 0, 64, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 11, 0,
-        0, 0, 120, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 13, 0, 0, 0, 184, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0,
-        0, 11, 0, 0, 0, 159, 235, 1, 0, 24, 0, 0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 4, 0, 0, 186, 6, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 2, 3, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 1, 4, 0, 0, 0, 32, 0, 0, 1, 0,
-        0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 0, 2, 0, 0, 0, 4, 0, 0, 0, 1, 0, 0, 0, 5, 0, 0, 0, 0, 0, 0,
-        1, 4, 0, 0, 0, 32, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 6, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3, 0,
-        0, 0, 0, 2, 0, 0, 0, 4, 0, 0, 0, 0, 16, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 8, 0, 0, 0, 25, 0, 0,
-        0, 0, 0, 0, 8, 9, 0, 0, 0, 29, 0, 0, 0, 0, 0, 0, 8, 10, 0, 0, 0, 35, 0, 0, 0, 0, 0, 0, 1,
-        8, 0, 0, 0, 64, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 12, 0, 0, 0, 54, 0, 0, 0, 3, 0, 0, 4, 150,
-        0, 0, 0, 62, 0, 0, 0, 14, 0, 0, 0, 0, 0, 0, 0, 67, 0, 0, 0, 14, 0, 0, 0, 144, 1, 0, 0, 72,
-        0, 0, 0, 14, 0, 0, 0, 32, 3, 0, 0, 78, 0, 0, 0, 0, 0, 0, 1, 1, 0, 0, 0, 8, 0, 0, 1, 0, 0,
-        0, 0, 0, 0, 0, 3, 0, 0, 0, 0, 13, 0, 0, 0, 4, 0, 0, 0, 50, 0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 4,
-        32, 0, 0, 0, 83, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 88, 0, 0, 0, 5, 0, 0, 0, 64, 0, 0, 0,
-        100, 0, 0, 0, 7, 0, 0, 0, 128, 0, 0, 0, 72, 0, 0, 0, 11, 0, 0, 0, 192, 0, 0, 0, 104, 0, 0,
-        0, 0, 0, 0, 14, 15, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 18, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 3, 0, 0, 0, 0, 2, 0, 0, 0, 4, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 20, 0,
-        0, 0, 112, 0, 0, 0, 0, 0, 0, 8, 21, 0, 0, 0, 116, 0, 0, 0, 0, 0, 0, 8, 22, 0, 0, 0, 122, 0,
-        0, 0, 0, 0, 0, 1, 4, 0, 0, 0, 32, 0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 4, 32, 0, 0, 0, 83, 0, 0,
-        0, 17, 0, 0, 0, 0, 0, 0, 0, 88, 0, 0, 0, 5, 0, 0, 0, 64, 0, 0, 0, 100, 0, 0, 0, 19, 0, 0,
-        0, 128, 0, 0, 0, 72, 0, 0, 0, 19, 0, 0, 0, 192, 0, 0, 0, 135, 0, 0, 0, 0, 0, 0, 14, 23, 0,
-        0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 26, 0, 0, 0, 142, 0, 0, 0, 4, 0, 0, 4, 64, 0, 0,
-        0, 168, 0, 0, 0, 27, 0, 0, 0, 0, 0, 0, 0, 172, 0, 0, 0, 30, 0, 0, 0, 64, 0, 0, 0, 175, 0,
-        0, 0, 32, 0, 0, 0, 128, 0, 0, 0, 180, 0, 0, 0, 33, 0, 0, 0, 0, 2, 0, 0, 187, 0, 0, 0, 4, 0,
-        0, 4, 8, 0, 0, 0, 83, 0, 0, 0, 28, 0, 0, 0, 0, 0, 0, 0, 199, 0, 0, 0, 29, 0, 0, 0, 16, 0,
-        0, 0, 205, 0, 0, 0, 29, 0, 0, 0, 24, 0, 0, 0, 219, 0, 0, 0, 2, 0, 0, 0, 32, 0, 0, 0, 223,
-        0, 0, 0, 0, 0, 0, 1, 2, 0, 0, 0, 16, 0, 0, 0, 238, 0, 0, 0, 0, 0, 0, 1, 1, 0, 0, 0, 8, 0,
-        0, 0, 252, 0, 0, 0, 0, 0, 0, 1, 8, 0, 0, 0, 64, 0, 0, 1, 1, 1, 0, 0, 0, 0, 0, 1, 8, 0, 0,
-        0, 64, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 0, 31, 0, 0, 0, 4, 0, 0, 0, 6, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 0, 13, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0,
-        13, 2, 0, 0, 0, 15, 1, 0, 0, 25, 0, 0, 0, 19, 1, 0, 0, 1, 0, 0, 12, 34, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 2, 37, 0, 0, 0, 34, 1, 0, 0, 4, 0, 0, 4, 24, 0, 0, 0, 168, 0, 0, 0, 27, 0, 0,
-        0, 0, 0, 0, 0, 172, 0, 0, 0, 30, 0, 0, 0, 64, 0, 0, 0, 59, 1, 0, 0, 30, 0, 0, 0, 128, 0, 0,
-        0, 180, 0, 0, 0, 33, 0, 0, 0, 192, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 13, 2, 0, 0, 0, 15, 1, 0,
-        0, 36, 0, 0, 0, 63, 1, 0, 0, 1, 0, 0, 12, 38, 0, 0, 0, 77, 1, 0, 0, 1, 0, 0, 12, 34, 0, 0,
-        0, 96, 1, 0, 0, 1, 0, 0, 12, 38, 0, 0, 0, 114, 1, 0, 0, 1, 0, 0, 12, 34, 0, 0, 0, 134, 1,
-        0, 0, 1, 0, 0, 12, 38, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 0, 13, 0, 0, 0, 4, 0, 0,
-        0, 13, 0, 0, 0, 153, 1, 0, 0, 0, 0, 0, 14, 44, 0, 0, 0, 1, 0, 0, 0, 10, 6, 0, 0, 2, 0, 0,
-        15, 64, 0, 0, 0, 16, 0, 0, 0, 0, 0, 0, 0, 32, 0, 0, 0, 24, 0, 0, 0, 32, 0, 0, 0, 32, 0, 0,
-        0, 16, 6, 0, 0, 1, 0, 0, 15, 13, 0, 0, 0, 45, 0, 0, 0, 0, 0, 0, 0, 13, 0, 0, 0, 0, 105,
-        110, 116, 0, 95, 95, 65, 82, 82, 65, 89, 95, 83, 73, 90, 69, 95, 84, 89, 80, 69, 95, 95, 0,
-        117, 54, 52, 0, 95, 95, 117, 54, 52, 0, 117, 110, 115, 105, 103, 110, 101, 100, 32, 108,
-        111, 110, 103, 32, 108, 111, 110, 103, 0, 101, 118, 101, 110, 116, 95, 116, 0, 112, 97,
-        116, 104, 0, 110, 97, 109, 101, 0, 118, 97, 108, 117, 101, 0, 99, 104, 97, 114, 0, 116,
-        121, 112, 101, 0, 109, 97, 120, 95, 101, 110, 116, 114, 105, 101, 115, 0, 107, 101, 121, 0,
-        115, 116, 111, 114, 97, 103, 101, 0, 117, 51, 50, 0, 95, 95, 117, 51, 50, 0, 117, 110, 115,
-        105, 103, 110, 101, 100, 32, 105, 110, 116, 0, 101, 118, 101, 110, 116, 115, 0, 116, 114,
-        97, 99, 101, 95, 101, 118, 101, 110, 116, 95, 114, 97, 119, 95, 115, 121, 115, 95, 101,
-        110, 116, 101, 114, 0, 101, 110, 116, 0, 105, 100, 0, 97, 114, 103, 115, 0, 95, 95, 100,
-        97, 116, 97, 0, 116, 114, 97, 99, 101, 95, 101, 110, 116, 114, 121, 0, 102, 108, 97, 103,
-        115, 0, 112, 114, 101, 101, 109, 112, 116, 95, 99, 111, 117, 110, 116, 0, 112, 105, 100, 0,
-        117, 110, 115, 105, 103, 110, 101, 100, 32, 115, 104, 111, 114, 116, 0, 117, 110, 115, 105,
-        103, 110, 101, 100, 32, 99, 104, 97, 114, 0, 108, 111, 110, 103, 0, 117, 110, 115, 105,
-        103, 110, 101, 100, 32, 108, 111, 110, 103, 0, 99, 116, 120, 0, 115, 101, 116, 95, 97, 116,
-        116, 114, 95, 101, 110, 116, 101, 114, 0, 116, 114, 97, 99, 101, 95, 101, 118, 101, 110,
-        116, 95, 114, 97, 119, 95, 115, 121, 115, 95, 101, 120, 105, 116, 0, 114, 101, 116, 0, 115,
-        101, 116, 95, 97, 116, 116, 114, 95, 101, 120, 105, 116, 0, 115, 121, 115, 95, 101, 110,
-        116, 101, 114, 95, 115, 101, 116, 120, 97, 116, 116, 114, 0, 115, 121, 115, 95, 101, 120,
-        105, 116, 95, 115, 101, 116, 120, 97, 116, 116, 114, 0, 115, 121, 115, 95, 101, 110, 116,
-        101, 114, 95, 108, 115, 101, 116, 120, 97, 116, 116, 114, 0, 115, 121, 115, 95, 101, 120,
-        105, 116, 95, 108, 115, 101, 116, 120, 97, 116, 116, 114, 0, 76, 73, 67, 69, 78, 83, 69, 0,
-        47, 104, 111, 109, 101, 47, 106, 97, 118, 105, 101, 114, 104, 111, 110, 100, 117, 99, 111,
-        47, 99, 111, 100, 101, 47, 115, 119, 101, 101, 112, 101, 114, 47, 46, 47, 115, 114, 99, 47,
-        115, 119, 101, 101, 112, 101, 114, 46, 98, 112, 102, 46, 99, 0, 105, 110, 116, 32, 115,
-        101, 116, 95, 97, 116, 116, 114, 95, 101, 110, 116, 101, 114, 40, 115, 116, 114, 117, 99,
-        116, 32, 116, 114, 97, 99, 101, 95, 101, 118, 101, 110, 116, 95, 114, 97, 119, 95, 115,
-        121, 115, 95, 101, 110, 116, 101, 114, 32, 42, 99, 116, 120, 41, 32, 123, 0, 32, 32, 115,
-        116, 114, 117, 99, 116, 32, 101, 118, 101, 110, 116, 95, 116, 32, 101, 118, 101, 110, 116,
-        32, 61, 32, 123, 48, 125, 59, 0, 32, 32, 98, 112, 102, 95, 112, 114, 111, 98, 101, 95, 114,
-        101, 97, 100, 95, 117, 115, 101, 114, 95, 115, 116, 114, 40, 101, 118, 101, 110, 116, 46,
-        112, 97, 116, 104, 44, 32, 115, 105, 122, 101, 111, 102, 40, 101, 118, 101, 110, 116, 46,
-        112, 97, 116, 104, 41, 44, 32, 40, 118, 111, 105, 100, 32, 42, 41, 99, 116, 120, 45, 62,
-        97, 114, 103, 115, 91, 48, 93, 41, 59, 0, 32, 32, 98, 112, 102, 95, 112, 114, 111, 98, 101,
-        95, 114, 101, 97, 100, 95, 117, 115, 101, 114, 95, 115, 116, 114, 40, 101, 118, 101, 110,
-        116, 46, 110, 97, 109, 101, 44, 32, 115, 105, 122, 101, 111, 102, 40, 101, 118, 101, 110,
-        116, 46, 110, 97, 109, 101, 41, 44, 32, 40, 118, 111, 105, 100, 32, 42, 41, 99, 116, 120,
-        45, 62, 97, 114, 103, 115, 91, 49, 93, 41, 59, 0, 32, 32, 32, 32, 32, 32, 32, 32, 32, 32,
-        32, 32, 32, 32, 32, 32, 32, 32, 32, 32, 32, 32, 32, 32, 32, 32, 40, 118, 111, 105, 100, 32,
-        42, 41, 99, 116, 120, 45, 62, 97, 114, 103, 115, 91, 50, 93, 41, 59, 0, 32, 32, 98, 112,
-        102, 95, 112, 114, 111, 98, 101, 95, 114, 101, 97, 100, 95, 117, 115, 101, 114, 95, 115,
-        116, 114, 40, 101, 118, 101, 110, 116, 46, 118, 97, 108, 117, 101, 44, 32, 115, 105, 122,
-        101, 111, 102, 40, 101, 118, 101, 110, 116, 46, 118, 97, 108, 117, 101, 41, 44, 0, 32, 32,
-        117, 54, 52, 32, 107, 101, 121, 32, 61, 32, 98, 112, 102, 95, 103, 101, 116, 95, 99, 117,
-        114, 114, 101, 110, 116, 95, 112, 105, 100, 95, 116, 103, 105, 100, 40, 41, 59, 0, 32, 32,
-        98, 112, 102, 95, 109, 97, 112, 95, 117, 112, 100, 97, 116, 101, 95, 101, 108, 101, 109,
-        40, 38, 115, 116, 111, 114, 97, 103, 101, 44, 32, 38, 107, 101, 121, 44, 32, 38, 101, 118,
-        101, 110, 116, 44, 32, 66, 80, 70, 95, 65, 78, 89, 41, 59, 0, 32, 32, 114, 101, 116, 117,
-        114, 110, 32, 48, 59, 0, 105, 110, 116, 32, 115, 101, 116, 95, 97, 116, 116, 114, 95, 101,
-        120, 105, 116, 40, 115, 116, 114, 117, 99, 116, 32, 116, 114, 97, 99, 101, 95, 101, 118,
-        101, 110, 116, 95, 114, 97, 119, 95, 115, 121, 115, 95, 101, 120, 105, 116, 32, 42, 99,
-        116, 120, 41, 32, 123, 0, 32, 32, 105, 102, 32, 40, 99, 116, 120, 45, 62, 114, 101, 116,
-        32, 33, 61, 32, 48, 41, 32, 123, 0, 32, 32, 115, 116, 114, 117, 99, 116, 32, 101, 118, 101,
-        110, 116, 95, 116, 32, 42, 101, 118, 101, 110, 116, 32, 61, 32, 98, 112, 102, 95, 109, 97,
-        112, 95, 108, 111, 111, 107, 117, 112, 95, 101, 108, 101, 109, 40, 38, 115, 116, 111, 114,
-        97, 103, 101, 44, 32, 38, 107, 101, 121, 41, 59, 0, 32, 32, 105, 102, 32, 40, 101, 118,
-        101, 110, 116, 32, 61, 61, 32, 78, 85, 76, 76, 41, 32, 123, 0, 32, 32, 98, 112, 102, 95,
-        112, 101, 114, 102, 95, 101, 118, 101, 110, 116, 95, 111, 117, 116, 112, 117, 116, 40, 99,
-        116, 120, 44, 32, 38, 101, 118, 101, 110, 116, 115, 44, 32, 66, 80, 70, 95, 70, 95, 67, 85,
-        82, 82, 69, 78, 84, 95, 67, 80, 85, 44, 32, 101, 118, 101, 110, 116, 44, 0, 32, 32, 98,
-        112, 102, 95, 109, 97, 112, 95, 100, 101, 108, 101, 116, 101, 95, 101, 108, 101, 109, 40,
-        38, 115, 116, 111, 114, 97, 103, 101, 44, 32, 38, 107, 101, 121, 41, 59, 0, 125, 0, 105,
-        110, 116, 32, 115, 121, 115, 95, 101, 110, 116, 101, 114, 95, 115, 101, 116, 120, 97, 116,
-        116, 114, 40, 115, 116, 114, 117, 99, 116, 32, 116, 114, 97, 99, 101, 95, 101, 118, 101,
-        110, 116, 95, 114, 97, 119, 95, 115, 121, 115, 95, 101, 110, 116, 101, 114, 32, 42, 99,
-        116, 120, 41, 32, 123, 0, 32, 32, 114, 101, 116, 117, 114, 110, 32, 115, 101, 116, 95, 97,
-        116, 116, 114, 95, 101, 110, 116, 101, 114, 40, 99, 116, 120, 41, 59, 0, 105, 110, 116, 32,
-        115, 121, 115, 95, 101, 120, 105, 116, 95, 115, 101, 116, 120, 97, 116, 116, 114, 40, 115,
-        116, 114, 117, 99, 116, 32, 116, 114, 97, 99, 101, 95, 101, 118, 101, 110, 116, 95, 114,
-        97, 119, 95, 115, 121, 115, 95, 101, 120, 105, 116, 32, 42, 99, 116, 120, 41, 32, 123, 0,
-        32, 32, 114, 101, 116, 117, 114, 110, 32, 115, 101, 116, 95, 97, 116, 116, 114, 95, 101,
-        120, 105, 116, 40, 99, 116, 120, 41, 59, 0, 105, 110, 116, 32, 115, 121, 115, 95, 101, 110,
-        116, 101, 114, 95, 108, 115, 101, 116, 120, 97, 116, 116, 114, 40, 115, 116, 114, 117, 99,
-        116, 32, 116, 114, 97, 99, 101, 95, 101, 118, 101, 110, 116, 95, 114, 97, 119, 95, 115,
-        121, 115, 95, 101, 110, 116, 101, 114, 32, 42, 99, 116, 120, 41, 32, 123, 0, 105, 110, 116,
-        32, 115, 121, 115, 95, 101, 120, 105, 116, 95, 108, 115, 101, 116, 120, 97, 116, 116, 114,
-        40, 115, 116, 114, 117, 99, 116, 32, 116, 114, 97, 99, 101, 95, 101, 118, 101, 110, 116,
-        95, 114, 97, 119, 95, 115, 121, 115, 95, 101, 120, 105, 116, 32, 42, 99, 116, 120, 41, 32,
-        123, 0, 48, 58, 50, 58, 48, 0, 48, 58, 50, 58, 49, 0, 48, 58, 50, 58, 50, 0, 48, 58, 50, 0,
-        46, 109, 97, 112, 115, 0, 108, 105, 99, 101, 110, 115, 101, 0, 46, 116, 101, 120, 116, 0,
-        116, 114, 97, 99, 101, 112, 111, 105, 110, 116, 47, 115, 121, 115, 99, 97, 108, 108, 115,
-        47, 115, 121, 115, 95, 101, 110, 116, 101, 114, 95, 115, 101, 116, 120, 97, 116, 116, 114,
-        0, 116, 114, 97, 99, 101, 112, 111, 105, 110, 116, 47, 115, 121, 115, 99, 97, 108, 108,
-        115, 47, 115, 121, 115, 95, 101, 120, 105, 116, 95, 115, 101, 116, 120, 97, 116, 116, 114,
-        0, 116, 114, 97, 99, 101, 112, 111, 105, 110, 116, 47, 115, 121, 115, 99, 97, 108, 108,
-        115, 47, 115, 121, 115, 95, 101, 110, 116, 101, 114, 95, 108, 115, 101, 116, 120, 97, 116,
-        116, 114, 0, 116, 114, 97, 99, 101, 112, 111, 105, 110, 116, 47, 115, 121, 115, 99, 97,
-        108, 108, 115, 47, 115, 121, 115, 95, 101, 120, 105, 116, 95, 108, 115, 101, 116, 120, 97,
-        116, 116, 114, 0, 0, 0, 0, 0, 0, 0, 159, 235, 1, 0, 32, 0, 0, 0, 0, 0, 0, 0, 92, 0, 0, 0,
-        92, 0, 0, 0, 60, 5, 0, 0, 152, 5, 0, 0, 236, 0, 0, 0, 8, 0, 0, 0, 24, 6, 0, 0, 2, 0, 0, 0,
-        0, 0, 0, 0, 35, 0, 0, 0, 136, 1, 0, 0, 39, 0, 0, 0, 30, 6, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0,
-        40, 0, 0, 0, 69, 6, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 41, 0, 0, 0, 107, 6, 0, 0, 1, 0, 0, 0, 0,
-        0, 0, 0, 42, 0, 0, 0, 147, 6, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 43, 0, 0, 0, 16, 0, 0, 0, 24,
-        6, 0, 0, 27, 0, 0, 0, 0, 0, 0, 0, 161, 1, 0, 0, 214, 1, 0, 0, 0, 112, 0, 0, 16, 0, 0, 0,
-        161, 1, 0, 0, 18, 2, 0, 0, 18, 116, 0, 0, 176, 0, 0, 0, 161, 1, 0, 0, 48, 2, 0, 0, 67, 128,
-        0, 0, 192, 0, 0, 0, 161, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 200, 0, 0, 0, 161, 1, 0, 0, 48,
-        2, 0, 0, 3, 128, 0, 0, 224, 0, 0, 0, 161, 1, 0, 0, 129, 2, 0, 0, 67, 132, 0, 0, 232, 0, 0,
-        0, 161, 1, 0, 0, 129, 2, 0, 0, 27, 132, 0, 0, 248, 0, 0, 0, 161, 1, 0, 0, 129, 2, 0, 0, 3,
-        132, 0, 0, 8, 1, 0, 0, 161, 1, 0, 0, 210, 2, 0, 0, 35, 140, 0, 0, 16, 1, 0, 0, 161, 1, 0,
-        0, 3, 3, 0, 0, 27, 136, 0, 0, 32, 1, 0, 0, 161, 1, 0, 0, 3, 3, 0, 0, 3, 136, 0, 0, 48, 1,
-        0, 0, 161, 1, 0, 0, 63, 3, 0, 0, 13, 164, 0, 0, 56, 1, 0, 0, 161, 1, 0, 0, 63, 3, 0, 0, 7,
-        164, 0, 0, 72, 1, 0, 0, 161, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 80, 1, 0, 0, 161, 1, 0, 0,
-        103, 3, 0, 0, 3, 168, 0, 0, 120, 1, 0, 0, 161, 1, 0, 0, 159, 3, 0, 0, 3, 176, 0, 0, 136, 1,
-        0, 0, 161, 1, 0, 0, 171, 3, 0, 0, 0, 188, 0, 0, 152, 1, 0, 0, 161, 1, 0, 0, 229, 3, 0, 0,
-        12, 192, 0, 0, 160, 1, 0, 0, 161, 1, 0, 0, 229, 3, 0, 0, 7, 192, 0, 0, 168, 1, 0, 0, 161,
-        1, 0, 0, 63, 3, 0, 0, 13, 208, 0, 0, 176, 1, 0, 0, 161, 1, 0, 0, 63, 3, 0, 0, 7, 208, 0, 0,
-        192, 1, 0, 0, 161, 1, 0, 0, 63, 3, 0, 0, 13, 208, 0, 0, 200, 1, 0, 0, 161, 1, 0, 0, 252, 3,
-        0, 0, 27, 212, 0, 0, 240, 1, 0, 0, 161, 1, 0, 0, 59, 4, 0, 0, 7, 216, 0, 0, 248, 1, 0, 0,
-        161, 1, 0, 0, 82, 4, 0, 0, 3, 232, 0, 0, 64, 2, 0, 0, 161, 1, 0, 0, 146, 4, 0, 0, 3, 244,
-        0, 0, 96, 2, 0, 0, 161, 1, 0, 0, 185, 4, 0, 0, 1, 252, 0, 0, 30, 6, 0, 0, 16, 0, 0, 0, 0,
-        0, 0, 0, 161, 1, 0, 0, 187, 4, 0, 0, 0, 12, 1, 0, 16, 0, 0, 0, 161, 1, 0, 0, 18, 2, 0, 0,
-        18, 116, 0, 0, 176, 0, 0, 0, 161, 1, 0, 0, 48, 2, 0, 0, 67, 128, 0, 0, 192, 0, 0, 0, 161,
-        1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 200, 0, 0, 0, 161, 1, 0, 0, 48, 2, 0, 0, 3, 128, 0, 0,
-        224, 0, 0, 0, 161, 1, 0, 0, 129, 2, 0, 0, 67, 132, 0, 0, 232, 0, 0, 0, 161, 1, 0, 0, 129,
-        2, 0, 0, 27, 132, 0, 0, 248, 0, 0, 0, 161, 1, 0, 0, 129, 2, 0, 0, 3, 132, 0, 0, 8, 1, 0, 0,
-        161, 1, 0, 0, 210, 2, 0, 0, 35, 140, 0, 0, 16, 1, 0, 0, 161, 1, 0, 0, 3, 3, 0, 0, 27, 136,
-        0, 0, 32, 1, 0, 0, 161, 1, 0, 0, 3, 3, 0, 0, 3, 136, 0, 0, 48, 1, 0, 0, 161, 1, 0, 0, 63,
-        3, 0, 0, 13, 164, 0, 0, 56, 1, 0, 0, 161, 1, 0, 0, 63, 3, 0, 0, 7, 164, 0, 0, 72, 1, 0, 0,
-        161, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 80, 1, 0, 0, 161, 1, 0, 0, 103, 3, 0, 0, 3, 168, 0,
-        0, 120, 1, 0, 0, 161, 1, 0, 0, 251, 4, 0, 0, 3, 16, 1, 0, 69, 6, 0, 0, 11, 0, 0, 0, 0, 0,
-        0, 0, 161, 1, 0, 0, 25, 5, 0, 0, 0, 32, 1, 0, 16, 0, 0, 0, 161, 1, 0, 0, 229, 3, 0, 0, 12,
-        192, 0, 0, 24, 0, 0, 0, 161, 1, 0, 0, 229, 3, 0, 0, 7, 192, 0, 0, 32, 0, 0, 0, 161, 1, 0,
-        0, 63, 3, 0, 0, 13, 208, 0, 0, 40, 0, 0, 0, 161, 1, 0, 0, 63, 3, 0, 0, 7, 208, 0, 0, 56, 0,
-        0, 0, 161, 1, 0, 0, 63, 3, 0, 0, 13, 208, 0, 0, 64, 0, 0, 0, 161, 1, 0, 0, 252, 3, 0, 0,
-        27, 212, 0, 0, 104, 0, 0, 0, 161, 1, 0, 0, 59, 4, 0, 0, 7, 216, 0, 0, 112, 0, 0, 0, 161, 1,
-        0, 0, 82, 4, 0, 0, 3, 232, 0, 0, 184, 0, 0, 0, 161, 1, 0, 0, 146, 4, 0, 0, 3, 244, 0, 0,
-        216, 0, 0, 0, 161, 1, 0, 0, 87, 5, 0, 0, 3, 36, 1, 0, 107, 6, 0, 0, 16, 0, 0, 0, 0, 0, 0,
-        0, 161, 1, 0, 0, 116, 5, 0, 0, 0, 56, 1, 0, 16, 0, 0, 0, 161, 1, 0, 0, 18, 2, 0, 0, 18,
-        116, 0, 0, 176, 0, 0, 0, 161, 1, 0, 0, 48, 2, 0, 0, 67, 128, 0, 0, 192, 0, 0, 0, 161, 1, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 200, 0, 0, 0, 161, 1, 0, 0, 48, 2, 0, 0, 3, 128, 0, 0, 224, 0,
-        0, 0, 161, 1, 0, 0, 129, 2, 0, 0, 67, 132, 0, 0, 232, 0, 0, 0, 161, 1, 0, 0, 129, 2, 0, 0,
-        27, 132, 0, 0, 248, 0, 0, 0, 161, 1, 0, 0, 129, 2, 0, 0, 3, 132, 0, 0, 8, 1, 0, 0, 161, 1,
-        0, 0, 210, 2, 0, 0, 35, 140, 0, 0, 16, 1, 0, 0, 161, 1, 0, 0, 3, 3, 0, 0, 27, 136, 0, 0,
-        32, 1, 0, 0, 161, 1, 0, 0, 3, 3, 0, 0, 3, 136, 0, 0, 48, 1, 0, 0, 161, 1, 0, 0, 63, 3, 0,
-        0, 13, 164, 0, 0, 56, 1, 0, 0, 161, 1, 0, 0, 63, 3, 0, 0, 7, 164, 0, 0, 72, 1, 0, 0, 161,
-        1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 80, 1, 0, 0, 161, 1, 0, 0, 103, 3, 0, 0, 3, 168, 0, 0,
-        120, 1, 0, 0, 161, 1, 0, 0, 251, 4, 0, 0, 3, 60, 1, 0, 147, 6, 0, 0, 11, 0, 0, 0, 0, 0, 0,
-        0, 161, 1, 0, 0, 181, 5, 0, 0, 0, 76, 1, 0, 16, 0, 0, 0, 161, 1, 0, 0, 229, 3, 0, 0, 12,
-        192, 0, 0, 24, 0, 0, 0, 161, 1, 0, 0, 229, 3, 0, 0, 7, 192, 0, 0, 32, 0, 0, 0, 161, 1, 0,
-        0, 63, 3, 0, 0, 13, 208, 0, 0, 40, 0, 0, 0, 161, 1, 0, 0, 63, 3, 0, 0, 7, 208, 0, 0, 56, 0,
-        0, 0, 161, 1, 0, 0, 63, 3, 0, 0, 13, 208, 0, 0, 64, 0, 0, 0, 161, 1, 0, 0, 252, 3, 0, 0,
-        27, 212, 0, 0, 104, 0, 0, 0, 161, 1, 0, 0, 59, 4, 0, 0, 7, 216, 0, 0, 112, 0, 0, 0, 161, 1,
-        0, 0, 82, 4, 0, 0, 3, 232, 0, 0, 184, 0, 0, 0, 161, 1, 0, 0, 146, 4, 0, 0, 3, 244, 0, 0,
-        216, 0, 0, 0, 161, 1, 0, 0, 87, 5, 0, 0, 3, 80, 1, 0, 16, 0, 0, 0, 24, 6, 0, 0, 4, 0, 0, 0,
-        176, 0, 0, 0, 26, 0, 0, 0, 244, 5, 0, 0, 0, 0, 0, 0, 224, 0, 0, 0, 26, 0, 0, 0, 250, 5, 0,
-        0, 0, 0, 0, 0, 8, 1, 0, 0, 26, 0, 0, 0, 0, 6, 0, 0, 0, 0, 0, 0, 152, 1, 0, 0, 37, 0, 0, 0,
-        6, 6, 0, 0, 0, 0, 0, 0, 30, 6, 0, 0, 3, 0, 0, 0, 176, 0, 0, 0, 26, 0, 0, 0, 244, 5, 0, 0,
-        0, 0, 0, 0, 224, 0, 0, 0, 26, 0, 0, 0, 250, 5, 0, 0, 0, 0, 0, 0, 8, 1, 0, 0, 26, 0, 0, 0,
-        0, 6, 0, 0, 0, 0, 0, 0, 69, 6, 0, 0, 1, 0, 0, 0, 16, 0, 0, 0, 37, 0, 0, 0, 6, 6, 0, 0, 0,
-        0, 0, 0, 107, 6, 0, 0, 3, 0, 0, 0, 176, 0, 0, 0, 26, 0, 0, 0, 244, 5, 0, 0, 0, 0, 0, 0,
-        224, 0, 0, 0, 26, 0, 0, 0, 250, 5, 0, 0, 0, 0, 0, 0, 8, 1, 0, 0, 26, 0, 0, 0, 0, 6, 0, 0,
-        0, 0, 0, 0, 147, 6, 0, 0, 1, 0, 0, 0, 16, 0, 0, 0, 37, 0, 0, 0, 6, 6, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 3, 0, 0, 0, 32, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 64, 0, 0, 0, 0, 0, 0, 0, 40, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 9, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 104, 2, 0, 0, 0, 0, 0, 0, 200, 1, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0,
-        8, 0, 0, 0, 0, 0, 0, 0, 24, 0, 0, 0, 0, 0, 0, 0, 17, 0, 0, 0, 1, 0, 0, 0, 6, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 48, 4, 0, 0, 0, 0, 0, 0, 104, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 23, 0, 0, 0, 1, 0, 0, 0, 6,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 152, 6, 0, 0, 0, 0, 0, 0, 136, 1, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 62, 0, 0, 0,
-        1, 0, 0, 0, 6, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 32, 8, 0, 0, 0, 0, 0, 0, 224,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 100, 0, 0, 0, 1, 0, 0, 0, 6, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 9, 0, 0, 0,
-        0, 0, 0, 136, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 140, 0, 0, 0, 1, 0, 0, 0, 6, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        136, 10, 0, 0, 0, 0, 0, 0, 224, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 8, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 179, 0, 0, 0, 1, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 104, 11, 0, 0, 0, 0, 0, 0, 64, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 185, 0, 0, 0, 1, 0, 0, 0, 3, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 168, 11, 0, 0, 0, 0, 0, 0, 13, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 100, 1, 0, 0, 9, 0, 0, 0,
-        64, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 184, 11, 0, 0, 0, 0, 0, 0, 64, 0, 0, 0, 0,
-        0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 16, 0, 0, 0, 0, 0, 0, 0, 110, 1,
-        0, 0, 9, 0, 0, 0, 64, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 248, 11, 0, 0, 0, 0, 0,
-        0, 16, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 4, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 16, 0, 0, 0, 0,
-        0, 0, 0, 153, 1, 0, 0, 9, 0, 0, 0, 64, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 8, 12,
-        0, 0, 0, 0, 0, 0, 48, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 5, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0,
-        16, 0, 0, 0, 0, 0, 0, 0, 195, 1, 0, 0, 9, 0, 0, 0, 64, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 56, 12, 0, 0, 0, 0, 0, 0, 16, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 6, 0, 0, 0, 8, 0,
-        0, 0, 0, 0, 0, 0, 16, 0, 0, 0, 0, 0, 0, 0, 239, 1, 0, 0, 9, 0, 0, 0, 64, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 72, 12, 0, 0, 0, 0, 0, 0, 48, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0,
-        7, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 16, 0, 0, 0, 0, 0, 0, 0, 26, 2, 0, 0, 1, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 120, 12, 0, 0, 0, 0, 0, 0, 210, 10, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 31, 2, 0, 0,
-        1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 80, 23, 0, 0, 0, 0, 0, 0, 164,
-        6, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0,
-    ];
-}
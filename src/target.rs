@@ -0,0 +1,225 @@
+use crate::escape::escape;
+use crate::xattr;
+use std::ffi::{CStr, CString, OsStr};
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::{AsRawFd, FromRawFd};
+use std::path::{Component, Path, PathBuf};
+
+/// A file reached from its parent directory, itself opened one component
+/// at a time without following symlinks. Everything done to the file goes
+/// through that directory, so a component swapped for a symlink once the
+/// file was checked can't send it elsewhere.
+pub struct Target {
+    path: PathBuf,
+    dir: File,
+    name: CString,
+}
+
+impl Target {
+    /// Fails if `path` isn't absolute and normalized, or if any of its
+    /// directories is a symlink. The file itself needn't exist.
+    pub fn open(path: &Path) -> io::Result<Target> {
+        let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+            return Err(invalid(path));
+        };
+
+        Ok(Target {
+            path: path.to_path_buf(),
+            dir: open_dir(parent, libc::O_PATH)?,
+            name: c_name(name)?,
+        })
+    }
+
+    /// Where the file was when opened, for messages and for what depends
+    /// on its location.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Opens the file with `flags`, or the symlink itself with `O_PATH`.
+    pub fn open_file(&self, flags: libc::c_int) -> io::Result<File> {
+        open_at(&self.dir, &self.name, flags, 0)
+    }
+
+    /// Creates the file, which mustn't exist yet, with `mode`.
+    pub fn create(&self, mode: libc::mode_t) -> io::Result<File> {
+        open_at(
+            &self.dir,
+            &self.name,
+            libc::O_WRONLY | libc::O_CREAT | libc::O_EXCL,
+            mode,
+        )
+    }
+
+    /// Of the file itself, not what it links to.
+    pub fn metadata(&self) -> io::Result<fs::Metadata> {
+        self.open_file(libc::O_PATH)?.metadata()
+    }
+
+    /// An extended attribute of the file, `None` for a symlink.
+    pub fn xattr(&self, name: &str) -> io::Result<Option<Vec<u8>>> {
+        let file = self.open_file(libc::O_PATH)?;
+        if file.metadata()?.file_type().is_symlink() {
+            return Ok(None);
+        }
+        xattr::get_fd(&file, name)
+    }
+
+    /// The file next to this one named like it followed by `suffix`.
+    pub fn sibling(&self, suffix: &str) -> io::Result<Target> {
+        let mut name = self.name.as_bytes().to_vec();
+        name.extend_from_slice(suffix.as_bytes());
+        let mut path = self.path.clone().into_os_string();
+        path.push(suffix);
+        Ok(Target {
+            path: PathBuf::from(path),
+            dir: self.dir.try_clone()?,
+            name: c_name(OsStr::from_bytes(&name))?,
+        })
+    }
+
+    pub fn unlink(&self) -> io::Result<()> {
+        if unsafe { libc::unlinkat(self.dir.as_raw_fd(), self.name.as_ptr(), 0) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Moves the file to where `other` is, replacing it.
+    pub fn rename_over(&self, other: &Target) -> io::Result<()> {
+        let ret = unsafe {
+            libc::renameat(
+                self.dir.as_raw_fd(),
+                self.name.as_ptr(),
+                other.dir.as_raw_fd(),
+                other.name.as_ptr(),
+            )
+        };
+        if ret < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Moves the file to `destination`, which is resolved as usual.
+    pub fn rename_to(&self, destination: &Path) -> io::Result<()> {
+        let destination = c_name(destination.as_os_str())?;
+        let ret = unsafe {
+            libc::renameat(
+                self.dir.as_raw_fd(),
+                self.name.as_ptr(),
+                libc::AT_FDCWD,
+                destination.as_ptr(),
+            )
+        };
+        if ret < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+}
+
+fn invalid(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("{} isn't a normalized absolute path", escape(path)),
+    )
+}
+
+/// Opens the directory at `path` with `flags`, one component at a time from
+/// `/`, failing if any of them is a symlink.
+pub fn open_dir(path: &Path, flags: libc::c_int) -> io::Result<File> {
+    if !path.is_absolute() {
+        return Err(invalid(path));
+    }
+    let mut names = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::RootDir => {}
+            Component::Normal(name) => names.push(c_name(name)?),
+            _ => return Err(invalid(path)),
+        }
+    }
+
+    let Some(last) = names.pop() else {
+        return OpenOptions::new()
+            .read(true)
+            .custom_flags(flags | libc::O_DIRECTORY)
+            .open("/");
+    };
+    let mut dir = OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_PATH | libc::O_DIRECTORY)
+        .open("/")?;
+    for name in names {
+        dir = open_at(&dir, &name, libc::O_PATH | libc::O_DIRECTORY, 0)?;
+    }
+    open_at(&dir, &last, flags | libc::O_DIRECTORY, 0)
+}
+
+fn c_name(name: &OsStr) -> io::Result<CString> {
+    CString::new(name.as_bytes()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
+
+/// Opens `name` in the directory `dir`, or the symlink itself if it is one
+/// and `flags` has `O_PATH`. Doesn't block on FIFOs either.
+pub fn open_at(
+    dir: &File,
+    name: &CStr,
+    flags: libc::c_int,
+    mode: libc::mode_t,
+) -> io::Result<File> {
+    let fd = unsafe {
+        libc::openat(
+            dir.as_raw_fd(),
+            name.as_ptr(),
+            flags | libc::O_NOFOLLOW | libc::O_CLOEXEC | libc::O_NONBLOCK,
+            libc::c_uint::from(mode),
+        )
+    };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(unsafe { File::from_raw_fd(fd) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+    use tempfile::TempDir;
+
+    #[test]
+    fn refuses_symlinked_directories() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        fs::create_dir(dir.join("real")).unwrap();
+        fs::write(dir.join("real/file"), b"").unwrap();
+        symlink(dir.join("real"), dir.join("link")).unwrap();
+
+        assert!(Target::open(&dir.join("link/file")).is_err());
+        assert!(Target::open(&dir.join("real/../real/file")).is_err());
+        assert!(Target::open(Path::new("real/file")).is_err());
+    }
+
+    #[test]
+    fn keeps_to_the_directory_it_opened() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        fs::create_dir(dir.join("sub")).unwrap();
+        fs::write(dir.join("sub/file"), b"").unwrap();
+        fs::create_dir(dir.join("elsewhere")).unwrap();
+        fs::write(dir.join("elsewhere/file"), b"").unwrap();
+
+        let target = Target::open(&dir.join("sub/file")).unwrap();
+        fs::rename(dir.join("sub"), dir.join("moved")).unwrap();
+        symlink(dir.join("elsewhere"), dir.join("sub")).unwrap();
+        target.unlink().unwrap();
+
+        assert!(!dir.join("moved/file").exists());
+        assert!(dir.join("elsewhere/file").exists());
+    }
+}
@@ -0,0 +1,389 @@
+use crate::action::DEFAULT_ACTION;
+use crate::rules::{parse_age, AgeBasis, Exclusion, Matcher, Rule};
+use glob::Pattern;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Same precedence as systemd-tmpfiles: a file in an earlier directory
+/// overrides a file with the same name in a later one.
+pub const TMPFILES_DIRS: [&str; 3] = ["/etc/tmpfiles.d", "/run/tmpfiles.d", "/usr/lib/tmpfiles.d"];
+
+// Without an explicit `age-by` prefix, systemd looks at every timestamp and
+// only cleans up a file when all of them are older than the age.
+const DEFAULT_AGES: [AgeBasis; 4] = [
+    AgeBasis::Atime,
+    AgeBasis::Btime,
+    AgeBasis::Ctime,
+    AgeBasis::Mtime,
+];
+
+/// A line that either schedules the contents of a directory or excludes
+/// paths from clean-up.
+enum Line {
+    Clean {
+        path: String,
+        ages: Vec<AgeBasis>,
+        age: String,
+        nested_only: bool,
+    },
+    Exclude(Exclusion),
+}
+
+fn has_wildcard(s: &str) -> bool {
+    s.contains(['*', '?', '['])
+}
+
+fn parse_age_field(field: &str) -> Result<(Vec<AgeBasis>, String, bool), String> {
+    let (ages, age) = match field.split_once(':') {
+        Some((by, age)) => {
+            let mut ages = Vec::new();
+            for c in by.chars() {
+                match c {
+                    'a' => ages.push(AgeBasis::Atime),
+                    'b' => ages.push(AgeBasis::Btime),
+                    'c' => ages.push(AgeBasis::Ctime),
+                    'm' => ages.push(AgeBasis::Mtime),
+                    // Uppercase letters apply to directories, which we never remove.
+                    'A' | 'B' | 'C' | 'M' => {}
+                    c => return Err(format!("unknown age-by specifier `{c}`")),
+                }
+            }
+            if ages.is_empty() {
+                ages = DEFAULT_AGES.to_vec();
+            }
+            (ages, age)
+        }
+        None => (DEFAULT_AGES.to_vec(), field),
+    };
+
+    let (age, nested_only) = match age.strip_prefix('~') {
+        Some(age) => (age, true),
+        None => (age, false),
+    };
+    parse_age(age)?;
+
+    Ok((ages, age.to_string(), nested_only))
+}
+
+fn parse_line(line: &str) -> Result<Option<Line>, String> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() < 2 {
+        return Err("missing path".to_string());
+    }
+
+    let kind = fields[0]
+        .trim_end_matches(['!', '-', '=', '~', '^', '+', '$'])
+        .to_string();
+    let path = fields[1];
+    if path.contains('%') {
+        return Err(format!("specifiers are not supported in `{path}`"));
+    }
+    if !path.starts_with('/') {
+        return Err(format!("path `{path}` is not absolute"));
+    }
+
+    match kind.as_str() {
+        "x" | "X" => {
+            let pattern = Pattern::new(path).map_err(|e| format!("invalid glob: {e}"))?;
+            Ok(Some(Line::Exclude(Exclusion {
+                pattern,
+                recursive: kind == "x",
+            })))
+        }
+        "d" | "D" | "e" => match fields.get(5) {
+            None | Some(&"-") => Ok(None),
+            Some(age) => {
+                let (ages, age, nested_only) = parse_age_field(age)?;
+                Ok(Some(Line::Clean {
+                    path: path.trim_end_matches('/').to_string(),
+                    ages,
+                    age,
+                    nested_only,
+                }))
+            }
+        },
+        _ => Ok(None),
+    }
+}
+
+fn clean_rule(
+    name: String,
+    path: &str,
+    ages: Vec<AgeBasis>,
+    age: &str,
+    nested_only: bool,
+) -> Result<Rule, String> {
+    let base = if has_wildcard(path) {
+        path.to_string()
+    } else {
+        Pattern::escape(path)
+    };
+    let matcher = if nested_only {
+        // Everything at least one directory below `path`.
+        Matcher::Glob(Pattern::new(&format!("{base}/*/**/*")).map_err(|e| e.to_string())?)
+    } else if has_wildcard(path) {
+        Matcher::Glob(Pattern::new(&format!("{base}/**/*")).map_err(|e| e.to_string())?)
+    } else {
+        Matcher::Prefix(PathBuf::from(path))
+    };
+
+    Ok(Rule {
+        name,
+        ages,
+        max_age: parse_age(age)?,
+        action: DEFAULT_ACTION.to_string(),
+        dry_run: false,
+        matcher,
+        exclude: Vec::new(),
+    })
+}
+
+/// Parses the contents of a single `tmpfiles.d` file. Lines that can't be
+/// honoured are reported as warnings and skipped, like systemd-tmpfiles
+/// does. Exclusions are returned separately as they apply to every file.
+fn parse_tmpfile(
+    contents: &str,
+    origin: &Path,
+    seen: &mut HashSet<String>,
+    rules: &mut Vec<Rule>,
+    exclusions: &mut Vec<Exclusion>,
+    warnings: &mut Vec<String>,
+) {
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let location = format!("{}:{}", origin.display(), i + 1);
+
+        match parse_line(line) {
+            Ok(Some(Line::Clean {
+                path,
+                ages,
+                age,
+                nested_only,
+            })) => {
+                if !seen.insert(path.clone()) {
+                    warnings.push(format!("{location}: duplicate line for `{path}`, ignoring"));
+                    continue;
+                }
+                let name = format!("tmpfiles:{location}");
+                match clean_rule(name, &path, ages, &age, nested_only) {
+                    Ok(rule) => rules.push(rule),
+                    Err(e) => warnings.push(format!("{location}: {e}")),
+                }
+            }
+            Ok(Some(Line::Exclude(exclusion))) => exclusions.push(exclusion),
+            Ok(None) => {}
+            Err(e) => warnings.push(format!("{location}: {e}")),
+        }
+    }
+}
+
+/// Loads the age directives of every `*.conf` file in `dirs` as rules,
+/// along with any warnings about lines that were skipped.
+pub fn load_tmpfiles(dirs: &[&str]) -> (Vec<Rule>, Vec<String>) {
+    let mut files: BTreeMap<String, PathBuf> = BTreeMap::new();
+    let mut warnings = Vec::new();
+
+    for dir in dirs {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => {
+                warnings.push(format!("{dir}: {e}"));
+                continue;
+            }
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "conf") {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                files.entry(name).or_insert(path);
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut rules = Vec::new();
+    let mut exclusions = Vec::new();
+    for file in files.values() {
+        match fs::read_to_string(file) {
+            Ok(contents) => parse_tmpfile(
+                &contents,
+                file,
+                &mut seen,
+                &mut rules,
+                &mut exclusions,
+                &mut warnings,
+            ),
+            Err(e) => warnings.push(format!("{}: {e}", file.display())),
+        }
+    }
+
+    for rule in &mut rules {
+        rule.exclude = exclusions.clone();
+    }
+
+    (rules, warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use AgeBasis::{Atime, Ctime, Mtime};
+
+    #[test]
+    fn parses_clean_lines() {
+        #[rustfmt::skip]
+        let cases: &[(&str, Option<(&str, &[AgeBasis], &str, bool)>)] = &[
+            ("d /tmp 1777 root root 10d", Some(("/tmp", &DEFAULT_AGES, "10d", false))),
+            ("D /var/tmp/ - - - 30d", Some(("/var/tmp", &DEFAULT_AGES, "30d", false))),
+            ("e /var/cache/app - - - 1w", Some(("/var/cache/app", &DEFAULT_AGES, "1w", false))),
+            ("D! /run/app - - - 1h", Some(("/run/app", &DEFAULT_AGES, "1h", false))),
+            ("d /srv/a - - - m:5d", Some(("/srv/a", &[Mtime], "5d", false))),
+            ("d /srv/b - - - cm:5d", Some(("/srv/b", &[Ctime, Mtime], "5d", false))),
+            ("d /srv/c - - - aM:5d", Some(("/srv/c", &[Atime], "5d", false))),
+            ("d /srv/d - - - M:5d", Some(("/srv/d", &DEFAULT_AGES, "5d", false))),
+            ("d /srv/e - - - ~2d", Some(("/srv/e", &DEFAULT_AGES, "2d", true))),
+            ("d /srv/f - - - m:~2d", Some(("/srv/f", &[Mtime], "2d", true))),
+            ("d /srv/g 0755 - - -", None),
+            ("d /srv/h 0755", None),
+            ("f /srv/i 0644 - - 10d", None),
+            ("L /srv/j - - - 10d", None),
+        ];
+        for (line, expected) in cases {
+            let parsed = match parse_line(line) {
+                Ok(Some(Line::Clean {
+                    path,
+                    ages,
+                    age,
+                    nested_only,
+                })) => Some((path, ages, age, nested_only)),
+                Ok(None) => None,
+                Ok(Some(Line::Exclude(_))) => panic!("{line}: parsed as an exclusion"),
+                Err(e) => panic!("{line}: {e}"),
+            };
+            let expected = expected.map(|(path, ages, age, nested_only)| {
+                (
+                    path.to_string(),
+                    ages.to_vec(),
+                    age.to_string(),
+                    nested_only,
+                )
+            });
+            assert_eq!(parsed, expected, "{line}");
+        }
+    }
+
+    #[test]
+    fn parses_exclusions() {
+        let cases = [
+            ("x /tmp/keep-*", "/tmp/keep-*", true),
+            ("X /tmp/only", "/tmp/only", false),
+        ];
+        for (line, pattern, recursive) in cases {
+            match parse_line(line) {
+                Ok(Some(Line::Exclude(exclusion))) => {
+                    assert_eq!(exclusion.pattern.as_str(), pattern, "{line}");
+                    assert_eq!(exclusion.recursive, recursive, "{line}");
+                }
+                _ => panic!("{line}: not parsed as an exclusion"),
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_lines() {
+        let cases = [
+            ("d", "missing path"),
+            ("d /run/user/%U - - - 1d", "specifiers are not supported"),
+            ("x /tmp/%b", "specifiers are not supported"),
+            ("d run/app - - - 1d", "is not absolute"),
+            ("d /srv/a - - - z:1d", "unknown age-by specifier"),
+            ("d /srv/a - - - 1x", "unknown unit"),
+        ];
+        for (line, error) in cases {
+            match parse_line(line) {
+                Err(e) => assert!(e.contains(error), "{line}: {e}"),
+                Ok(_) => panic!("{line}: accepted"),
+            }
+        }
+    }
+
+    #[test]
+    fn matches_what_systemd_cleans() {
+        let rule = |line| match parse_line(line) {
+            Ok(Some(Line::Clean {
+                path,
+                ages,
+                age,
+                nested_only,
+            })) => clean_rule(line.to_string(), &path, ages, &age, nested_only).unwrap(),
+            _ => panic!("{line}: not a clean line"),
+        };
+
+        let all = rule("d /srv/all - - - 1d");
+        assert!(all.matches(Path::new("/srv/all/a")));
+        assert!(all.matches(Path::new("/srv/all/a/b/c")));
+        assert!(!all.matches(Path::new("/srv/allx/a")));
+
+        let nested = rule("d /srv/nested - - - ~1d");
+        assert!(!nested.matches(Path::new("/srv/nested/a")));
+        assert!(nested.matches(Path::new("/srv/nested/a/b")));
+        assert!(nested.matches(Path::new("/srv/nested/a/b/c")));
+
+        let wildcard = rule("d /srv/build-* - - - 1d");
+        assert!(!wildcard.matches(Path::new("/srv/build-1")));
+        assert!(wildcard.matches(Path::new("/srv/build-1/a")));
+        assert!(wildcard.matches(Path::new("/srv/build-1/a/b")));
+        assert!(!wildcard.matches(Path::new("/srv/other/build-1/a")));
+    }
+
+    #[test]
+    fn overrides_like_systemd() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        let [etc, run, lib] = ["etc", "run", "lib"].map(|dir| {
+            let dir = root.join(dir);
+            fs::create_dir_all(&dir).unwrap();
+            dir
+        });
+        fs::write(etc.join("a.conf"), "d /srv/a - - - 1d\n").unwrap();
+        fs::write(run.join("a.conf"), "d /srv/a - - - 2d\n").unwrap();
+        fs::write(lib.join("a.conf"), "d /srv/a - - - 3d\n").unwrap();
+        fs::write(run.join("b.conf"), "d /srv/b - - - 4d\n").unwrap();
+        fs::write(lib.join("b.conf"), "d /srv/b - - - 5d\n").unwrap();
+        fs::write(lib.join("c.conf"), "d /srv/c - - - 6d\nx /srv/*/keep\n").unwrap();
+        // Ignored, it isn't a `.conf`.
+        fs::write(etc.join("c.conf.orig"), "d /srv/c - - - 7d\n").unwrap();
+        // Only the first line for a path counts.
+        fs::write(
+            lib.join("d.conf"),
+            "d /srv/a - - - 8d\nd /srv/%h - - - 1d\n",
+        )
+        .unwrap();
+
+        let dirs = [&etc, &run, &lib].map(|dir| dir.to_str().unwrap().to_string());
+        let dirs: Vec<&str> = dirs.iter().map(String::as_str).collect();
+        let (rules, warnings) = load_tmpfiles(&dirs);
+
+        let days: Vec<u64> = rules
+            .iter()
+            .map(|rule| rule.max_age.as_secs() / (24 * 60 * 60))
+            .collect();
+        assert_eq!(days, [1, 4, 6]);
+        assert_eq!(
+            rules[0].name,
+            format!("tmpfiles:{}:1", etc.join("a.conf").display())
+        );
+        assert!(rules.iter().all(
+            |rule| rule.exclude.len() == 1 && rule.exclude[0].pattern.as_str() == "/srv/*/keep"
+        ));
+        assert_eq!(warnings.len(), 2, "{warnings:?}");
+        assert!(warnings[0].contains("duplicate line for `/srv/a`"));
+        assert!(warnings[1].contains("specifiers are not supported"));
+    }
+}
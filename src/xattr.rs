@@ -0,0 +1,235 @@
+use std::ffi::CString;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+
+/// Extended attribute holding the epoch at which a file expires.
+pub const EXPIRE_AT: &str = "user.expire_at";
+/// Extended attribute selecting what happens to a file once it expires.
+pub const EXPIRE_ACTION: &str = "user.expire_action";
+
+fn c_string(bytes: &[u8]) -> io::Result<CString> {
+    CString::new(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
+
+type GetXattr = unsafe extern "C" fn(
+    *const libc::c_char,
+    *const libc::c_char,
+    *mut libc::c_void,
+    libc::size_t,
+) -> libc::ssize_t;
+
+fn read(c_path: &CString, name: &str, getxattr: GetXattr) -> io::Result<Option<Vec<u8>>> {
+    let c_name = c_string(name.as_bytes())?;
+
+    loop {
+        let size = unsafe { getxattr(c_path.as_ptr(), c_name.as_ptr(), std::ptr::null_mut(), 0) };
+        if size < 0 {
+            let err = io::Error::last_os_error();
+            return match err.raw_os_error() {
+                Some(libc::ENODATA) => Ok(None),
+                _ => Err(err),
+            };
+        }
+
+        let mut value = vec![0u8; size as usize];
+        let read = unsafe {
+            getxattr(
+                c_path.as_ptr(),
+                c_name.as_ptr(),
+                value.as_mut_ptr() as *mut libc::c_void,
+                value.len(),
+            )
+        };
+        if read < 0 {
+            let err = io::Error::last_os_error();
+            match err.raw_os_error() {
+                Some(libc::ENODATA) => return Ok(None),
+                // The value grew between both calls.
+                Some(libc::ERANGE) => continue,
+                _ => return Err(err),
+            }
+        }
+
+        value.truncate(read as usize);
+        return Ok(Some(value));
+    }
+}
+
+/// Reads an extended attribute without following symlinks. Returns `None`
+/// if the file doesn't have it.
+pub fn get(path: &Path, name: &str) -> io::Result<Option<Vec<u8>>> {
+    read(
+        &c_string(path.as_os_str().as_bytes())?,
+        name,
+        libc::lgetxattr,
+    )
+}
+
+/// Sets an extended attribute without following symlinks.
+pub fn set(path: &Path, name: &str, value: &[u8]) -> io::Result<()> {
+    let c_path = c_string(path.as_os_str().as_bytes())?;
+    let c_name = c_string(name.as_bytes())?;
+
+    let ret = unsafe {
+        libc::lsetxattr(
+            c_path.as_ptr(),
+            c_name.as_ptr(),
+            value.as_ptr() as *const libc::c_void,
+            value.len(),
+            0,
+        )
+    };
+    if ret < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Sets an extended attribute of an open file.
+pub fn fset(file: &File, name: &str, value: &[u8]) -> io::Result<()> {
+    let c_name = c_string(name.as_bytes())?;
+
+    let ret = unsafe {
+        libc::fsetxattr(
+            file.as_raw_fd(),
+            c_name.as_ptr(),
+            value.as_ptr() as *const libc::c_void,
+            value.len(),
+            0,
+        )
+    };
+    if ret < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Removes an extended attribute without following symlinks. Returns
+/// whether the file had it.
+pub fn remove(path: &Path, name: &str) -> io::Result<bool> {
+    let c_path = c_string(path.as_os_str().as_bytes())?;
+    let c_name = c_string(name.as_bytes())?;
+
+    if unsafe { libc::lremovexattr(c_path.as_ptr(), c_name.as_ptr()) } < 0 {
+        let err = io::Error::last_os_error();
+        return match err.raw_os_error() {
+            Some(libc::ENODATA) => Ok(false),
+            _ => Err(err),
+        };
+    }
+    Ok(true)
+}
+
+/// Opens the file at `path` to get at its xattrs through the `*_fd` functions,
+/// which then act on that very file however its path changes.
+/// Symlinks are refused rather than followed.
+pub fn open(path: &Path) -> io::Result<File> {
+    let file = OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_PATH | libc::O_NOFOLLOW)
+        .open(path)?;
+    if file.metadata()?.file_type().is_symlink() {
+        return Err(io::Error::from_raw_os_error(libc::ELOOP));
+    }
+    Ok(file)
+}
+
+// `f*xattr` refuse `O_PATH` fds, but the fd's entry in /proc leads to the
+// file itself without resolving its path again.
+fn proc_path(file: &File) -> io::Result<CString> {
+    c_string(format!("/proc/self/fd/{}", file.as_raw_fd()).as_bytes())
+}
+
+/// Reads an extended attribute of a file from `open`. Returns `None` if
+/// the file doesn't have it.
+pub fn get_fd(file: &File, name: &str) -> io::Result<Option<Vec<u8>>> {
+    read(&proc_path(file)?, name, libc::getxattr)
+}
+
+/// Sets an extended attribute of a file from `open`.
+pub fn set_fd(file: &File, name: &str, value: &[u8]) -> io::Result<()> {
+    let c_path = proc_path(file)?;
+    let c_name = c_string(name.as_bytes())?;
+
+    let ret = unsafe {
+        libc::setxattr(
+            c_path.as_ptr(),
+            c_name.as_ptr(),
+            value.as_ptr() as *const libc::c_void,
+            value.len(),
+            0,
+        )
+    };
+    if ret < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Removes an extended attribute of a file from `open`. Returns whether
+/// the file had it.
+pub fn remove_fd(file: &File, name: &str) -> io::Result<bool> {
+    let c_path = proc_path(file)?;
+    let c_name = c_string(name.as_bytes())?;
+
+    if unsafe { libc::removexattr(c_path.as_ptr(), c_name.as_ptr()) } < 0 {
+        let err = io::Error::last_os_error();
+        return match err.raw_os_error() {
+            Some(libc::ENODATA) => Ok(false),
+            _ => Err(err),
+        };
+    }
+    Ok(true)
+}
+
+/// Parses a `user.expire_at` value the way the daemon does: a timestamp in
+/// seconds, surrounding whitespace and trailing NULs allowed.
+pub fn parse_expire_at(value: &[u8]) -> Option<i64> {
+    std::str::from_utf8(value)
+        .ok()?
+        .trim_end_matches('\0')
+        .trim()
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn changes_the_opened_file_even_once_moved() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("bye");
+        fs::write(&path, b"").unwrap();
+        let file = open(&path).unwrap();
+        let moved = tmp.path().join("moved");
+        fs::rename(&path, &moved).unwrap();
+        fs::write(&path, b"").unwrap();
+
+        set_fd(&file, EXPIRE_AT, b"42").unwrap();
+        assert_eq!(get(&moved, EXPIRE_AT).unwrap(), Some(b"42".to_vec()));
+        assert_eq!(get(&path, EXPIRE_AT).unwrap(), None);
+
+        assert!(remove_fd(&file, EXPIRE_AT).unwrap());
+        assert!(!remove_fd(&file, EXPIRE_AT).unwrap());
+        assert_eq!(get(&moved, EXPIRE_AT).unwrap(), None);
+    }
+
+    #[test]
+    fn refuses_to_open_symlinks() {
+        let tmp = TempDir::new().unwrap();
+        let target = tmp.path().join("target");
+        fs::write(&target, b"").unwrap();
+        let link = tmp.path().join("link");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        assert_eq!(open(&link).unwrap_err().raw_os_error(), Some(libc::ELOOP));
+    }
+}